@@ -1,33 +1,25 @@
-use std::thread;
+use std::{thread, time::Duration};
 
 fn get_frame(source: &ndi::Source) -> ndi::VideoData {
     let mut recv = ndi::RecvBuilder::new().build().unwrap();
     recv.connect(source);
 
-    let mut video_data = None;
     loop {
-        let response = recv.capture_video(&mut video_data, 1000);
-        if response == ndi::FrameType::Video {
-            break;
+        if let Some(video_data) = recv.capture_video(Duration::from_secs(1)).unwrap() {
+            return video_data;
         }
     }
-
-    video_data.expect("no video data")
 }
 
 fn main() {
-    ndi::initialize().unwrap();
+    let _runtime = ndi::NdiRuntime::initialize().unwrap();
 
     let find = ndi::FindBuilder::new().build().unwrap();
-    let sources = find.current_sources(1000).unwrap();
+    let sources = find.current_sources(Duration::from_secs(1)).unwrap();
 
     let frame = get_frame(&sources[0]);
 
-    thread::sleep(std::time::Duration::from_millis(1000));
+    thread::sleep(Duration::from_millis(1000));
 
     println!("Frame received: {}x{}", frame.width(), frame.height());
-
-    unsafe {
-        ndi::cleanup();
-    }
 }
@@ -1,14 +1,14 @@
 use std::{
     sync::{mpsc::channel, Arc},
     thread,
-    time::Instant,
+    time::{Duration, Instant},
 };
 
 fn main() {
-    ndi::initialize().unwrap();
+    let _runtime = ndi::NdiRuntime::initialize().unwrap();
     let find = ndi::FindBuilder::new().build().unwrap();
 
-    let sources = find.current_sources(1000).unwrap();
+    let sources = find.current_sources(Duration::from_secs(1)).unwrap();
 
     let mut recv = ndi::RecvBuilder::new().build().unwrap();
     println!("Connecting to the first source: {}", sources[0].get_name());
@@ -23,12 +23,9 @@ fn main() {
         println!("Running video capture in thread 1");
         let start = Instant::now();
         while Instant::now().duration_since(start).as_millis() < 10000 {
-            let mut video_data = None;
-            let response = video_arc.capture_video(&mut video_data, 1000);
-            if response == ndi::FrameType::Video {
-                if let Some(video) = video_data {
-                    video_tx.send(video).unwrap()
-                }
+            let video_data = video_arc.capture_video(Duration::from_secs(1)).unwrap();
+            if let Some(video) = video_data {
+                video_tx.send(video).unwrap()
             }
         }
     });
@@ -39,12 +36,9 @@ fn main() {
         println!("Running audio capture in thread 2");
         let start = Instant::now();
         while Instant::now().duration_since(start).as_millis() < 10000 {
-            let mut audio_data = None;
-            let response = recv.capture_audio(&mut audio_data, 1000);
-            if response == ndi::FrameType::Audio {
-                if let Some(audio) = audio_data {
-                    audio_tx.send(audio).unwrap()
-                }
+            let audio_data = recv.capture_audio(Duration::from_secs(1)).unwrap();
+            if let Some(audio) = audio_data {
+                audio_tx.send(audio).unwrap()
             }
         }
     });
@@ -66,8 +60,4 @@ fn main() {
             );
         }
     }
-
-    unsafe {
-        ndi::cleanup();
-    }
 }
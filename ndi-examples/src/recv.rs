@@ -1,12 +1,13 @@
 use std::io;
-use std::time::Instant;
+use std::time::{Duration, Instant};
 
 fn main() {
-    ndi::initialize().unwrap();
+    let runtime = ndi::NdiRuntime::initialize().unwrap();
+    println!("Using {}", runtime.version());
 
     let find = ndi::Find::new().unwrap();
     println!("Looking for sources");
-    let sources = find.current_sources(1000).unwrap();
+    let sources = find.current_sources(Duration::from_secs(1)).unwrap();
 
     if sources.len() == 0 {
         panic!("No sources found");
@@ -26,6 +27,7 @@ fn main() {
         .color_format(ndi::RecvColorFormat::RGBX_RGBA)
         .ndi_recv_name("ndi-rs".to_string());
     let mut recv = recv_builder.build().unwrap();
+    let connection_events = recv.connection_events();
     recv.connect(&sources[i]);
 
     let name = sources[i].get_name();
@@ -33,18 +35,17 @@ fn main() {
 
     let start = Instant::now();
     while Instant::now().duration_since(start).as_secs() < 5 {
-        let mut video_data = None;
-        let mut audio_data = None;
-        let mut meta_data = None;
-        let response = recv.capture_all(&mut video_data, &mut audio_data, &mut meta_data, 1000);
+        let response = recv.capture(Duration::from_secs(1));
+        for state in connection_events.try_iter() {
+            println!("Connection state: {:?}", state);
+        }
 
         let (total, dropped) = recv.get_performance();
         println!("total:\n {}dropped:\n {}", total, dropped);
 
         match response {
-            ndi::FrameType::None => println!("Nothing"),
-            ndi::FrameType::Video => {
-                let video_data = video_data.expect("Failed to get video data from capture");
+            Ok(ndi::Frame::None) => println!("Nothing"),
+            Ok(ndi::Frame::Video(video_data)) => {
                 println!(
                     "Got video data: {}x{} {:?}",
                     video_data.width(),
@@ -52,8 +53,7 @@ fn main() {
                     video_data.four_cc()
                 );
             }
-            ndi::FrameType::Audio => {
-                let audio_data = audio_data.expect("Failed to get audio data from capture");
+            Ok(ndi::Frame::Audio(audio_data)) => {
                 println!(
                     "Got audio data. Channels: {}, Samples: {}, Stride: {}",
                     audio_data.no_channels(),
@@ -61,27 +61,22 @@ fn main() {
                     audio_data.channel_stride_in_bytes()
                 );
             }
-            ndi::FrameType::StatusChange => {
+            Ok(ndi::Frame::StatusChange) => {
                 println!("Status change.")
             }
-            ndi::FrameType::ErrorFrame => {
-                println!("Error")
-            }
-            ndi::FrameType::Metadata => {
-                let meta_data = meta_data.expect("Failed to get meta data from capture");
+            Ok(ndi::Frame::Metadata(meta_data)) => {
                 println!("Got metadata. {:?}", meta_data.length())
             }
+            Err(e) => {
+                println!("Error: {}", e)
+            }
         }
     }
 
     let meta_str = "Hello World".to_owned();
-    let meta = ndi::MetaData::new(0, 0, meta_str);
+    let meta = ndi::MetaData::new(0, 0, meta_str).unwrap();
 
     println!("{}", meta.data());
 
     println!("Done");
-
-    unsafe {
-        ndi::cleanup();
-    }
 }
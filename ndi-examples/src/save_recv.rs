@@ -1,11 +1,12 @@
 extern crate ndi;
-use std::iter::FromIterator;
+
+use std::time::Duration;
 
 fn main() {
-    ndi::initialize().unwrap();
+    let _runtime = ndi::NdiRuntime::initialize().unwrap();
 
     let find = ndi::FindBuilder::new().build().unwrap();
-    let sources = find.current_sources(1000).unwrap();
+    let sources = find.current_sources(Duration::from_secs(1)).unwrap();
 
     let mut recv = ndi::RecvBuilder::new()
         .color_format(ndi::RecvColorFormat::RGBX_RGBA)
@@ -13,22 +14,13 @@ fn main() {
         .unwrap();
     recv.connect(&sources[0]);
 
-    let mut video_data = None;
-    loop {
-        let res = recv.capture_video(&mut video_data, 1000);
-        if res == ndi::FrameType::Video {
-            break;
+    let frame = loop {
+        if let Some(video_data) = recv.capture_video(Duration::from_secs(1)).unwrap() {
+            break video_data;
         }
-    }
-
-    let frame = video_data.unwrap();
-
-    let frame_vec = unsafe {
-        assert!(!frame.p_data().is_null());
-        let size = frame.height() * frame.line_stride_in_bytes().unwrap();
-        std::slice::from_raw_parts(frame.p_data(), size as _)
     };
-    let frame_vec = Vec::from_iter(frame_vec.to_owned());
+
+    let frame_vec = frame.rows(0).collect::<Vec<_>>().concat();
     let buf = image::ImageBuffer::<image::Rgba<u8>, Vec<_>>::from_vec(
         frame.width(),
         frame.height(),
@@ -39,9 +31,5 @@ fn main() {
 
     buf.save("save_recv.png").unwrap();
 
-    unsafe {
-        ndi::cleanup();
-    }
-
     println!("Done");
 }
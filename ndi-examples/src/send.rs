@@ -1,7 +1,7 @@
-use std::iter::FromIterator;
+use std::{thread, time::Duration};
 
 fn main() {
-    ndi::initialize().unwrap();
+    let _runtime = ndi::NdiRuntime::initialize().unwrap();
 
     let send = ndi::SendBuilder::new()
         .ndi_name("MySender".to_string())
@@ -23,7 +23,7 @@ fn main() {
         };
     }
 
-    let video_data_send = ndi::VideoData::from_buffer(
+    let video_data_send = ndi::VideoFrameRef::from_buffer(
         1920,
         1080,
         ndi::FourCCVideoType::RGBA,
@@ -33,13 +33,14 @@ fn main() {
         0,
         0,
         None,
-        buf.as_mut_slice(),
-    );
+        buf.as_slice(),
+    )
+    .unwrap();
 
     println!("Made video data");
 
     let find = ndi::FindBuilder::new().build().unwrap();
-    let sources = find.current_sources(1000).unwrap();
+    let sources = find.current_sources(Duration::from_secs(1)).unwrap();
 
     let mut recv = ndi::RecvBuilder::new()
         .color_format(ndi::RecvColorFormat::RGBX_RGBA)
@@ -56,19 +57,17 @@ fn main() {
 
     println!("Source: {}", sources[idx].get_name());
 
-    let num_connected_to_sender = send.get_no_connections(1000);
-    println!("Receivers on sender: {}", num_connected_to_sender);
+    // the handle can be used from another thread while this one sends video
+    let handle = send.handle();
+    let connections = thread::spawn(move || handle.get_no_connections(1000));
+    println!("Receivers on sender: {}", connections.join().unwrap());
 
-    let mut video_data = None;
-    loop {
-        send.send_video(&video_data_send);
-        let res = recv.capture_video(&mut video_data, 1000);
-        if res == ndi::FrameType::Video {
-            break;
+    let frame = loop {
+        send.send_video(video_data_send);
+        if let Some(video_data) = recv.capture_video(Duration::from_secs(1)).unwrap() {
+            break video_data;
         }
-    }
-
-    let frame = video_data.unwrap();
+    };
 
     println!(
         "Got video data: {}x{} {:?} {} {:?} {:?} {}",
@@ -82,12 +81,7 @@ fn main() {
     );
 
     // save result to png, same as save_recv example
-    let frame_vec = unsafe {
-        assert!(!frame.p_data().is_null());
-        let size = frame.height() * frame.line_stride_in_bytes().unwrap();
-        std::slice::from_raw_parts(frame.p_data(), size as _)
-    };
-    let frame_vec = Vec::from_iter(frame_vec.to_owned());
+    let frame_vec = frame.rows(0).collect::<Vec<_>>().concat();
     let buf = image::ImageBuffer::<image::Rgba<u8>, Vec<_>>::from_vec(
         frame.width(),
         frame.height(),
@@ -98,9 +92,5 @@ fn main() {
 
     buf.save("save_recv.png").unwrap();
 
-    unsafe {
-        ndi::cleanup();
-    }
-
     println!("Done");
 }
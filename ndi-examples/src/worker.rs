@@ -0,0 +1,36 @@
+use std::time::{Duration, Instant};
+
+fn main() {
+    let _runtime = ndi::NdiRuntime::initialize().unwrap();
+    let find = ndi::FindBuilder::new().build().unwrap();
+
+    let sources = find.current_sources(Duration::from_secs(1)).unwrap();
+
+    let mut recv = ndi::RecvBuilder::new().build().unwrap();
+    println!("Connecting to the first source: {}", sources[0].get_name());
+    recv.connect(&sources[0]);
+
+    let worker = ndi::RecvWorkerBuilder::new()
+        .capacity(2)
+        .drop_policy(ndi::DropPolicy::DropOldest)
+        .metadata(false)
+        .build(recv);
+
+    let start = Instant::now();
+    while Instant::now().duration_since(start).as_secs() < 5 {
+        if let Some(video_data) = worker.recv_video(Duration::from_secs(1)) {
+            println!(
+                "Received video: {}x{}",
+                video_data.width(),
+                video_data.height()
+            );
+        }
+
+        // only take some of the audio, so the queue overflows
+        if let Some(audio_data) = worker.recv_audio(Duration::ZERO) {
+            println!("Received audio: {}", audio_data.no_channels());
+        }
+    }
+
+    println!("{}", worker.get_performance());
+}
@@ -82,6 +82,11 @@ fn macos_link_and_load() {
 }
 
 fn main() {
+    // the runtime is loaded with dlopen, so neither the SDK nor the library are needed at build time
+    if env::var_os("CARGO_FEATURE_DYNAMIC_LINK").is_some() {
+        return;
+    }
+
     let os = env::var("CARGO_CFG_TARGET_OS").unwrap();
     match os.as_str() {
         "windows" => win_link_and_load(),
@@ -12,32 +12,36 @@ macro_rules! impl_error {
     };
 }
 
-/// The system is not compatible with NDI
+/// The error type used in this crate
 #[derive(Debug)]
-pub struct NotSupported;
-impl_error!(NotSupported);
-
-/// Failed to convert a c `int` into an `enum`
-#[derive(Debug)]
-pub struct InvalidEnum(pub i32, pub &'static str);
-impl_error!(InvalidEnum);
-
-/// Failed to create an instance of Recv
-#[derive(Debug)]
-pub struct RecvCreateError;
-impl_error!(RecvCreateError);
-
-/// Failed to create an instance of Find
-#[derive(Debug)]
-pub struct FindCreateError;
-impl_error!(FindCreateError);
-
-/// Failed to create an instance of Send
-#[derive(Debug)]
-pub struct SendCreateError;
-impl_error!(SendCreateError);
+pub enum Error {
+    /// The CPU or the connected source doesn't support the operation
+    NotSupported,
+    /// The NDI runtime could not be loaded, only returned with the `dynamic-link` feature
+    LoadFailed(String),
+    /// The SDK failed to create an instance of the named type
+    CreateFailed(&'static str),
+    /// Failed to convert a c `int` into the named `enum`
+    InvalidEnum(i32, &'static str),
+    /// A string passed to the SDK contains an interior NUL byte
+    InteriorNul(NulError),
+    /// The instance is not connected to any source
+    NotConnected,
+    /// The operation did not complete within the timeout
+    Timeout,
+    /// The named value is outside of the range accepted by the SDK
+    OutOfRange(&'static str, f32),
+    /// The connected source is not recording
+    NotRecording,
+    /// The frame doesn't hold any data
+    NoData,
+    /// A buffer or stride doesn't match the layout of the frame
+    InvalidLayout,
+}
+impl_error!(Error);
 
-/// Findng the current sources timed out
-#[derive(Debug)]
-pub struct FindSourcesTimeout;
-impl_error!(FindSourcesTimeout);
+impl From<NulError> for Error {
+    fn from(e: NulError) -> Self {
+        Error::InteriorNul(e)
+    }
+}
@@ -1,7 +1,12 @@
 use crate::internal::OnDrop;
 
 use super::*;
-use std::{ffi::CString, thread::yield_now, time::Instant};
+use std::{
+    collections::VecDeque,
+    ffi::CString,
+    sync::Mutex,
+    time::{Duration, Instant},
+};
 
 /// Builder for [`Find`] struct
 #[derive(Debug, Clone)]
@@ -53,7 +58,7 @@ impl FindBuilder {
     }
 
     /// Build an instance of [`Find`]
-    pub fn build(self) -> Result<Find, FindCreateError> {
+    pub fn build(self) -> Result<Find, Error> {
         // from default c++ constructor in Processing.NDI.Find.h
         let mut settings = NDIlib_find_create_t {
             show_local_sources: true,
@@ -66,12 +71,12 @@ impl FindBuilder {
         }
 
         if let Some(groups) = self.groups {
-            let cstr = CString::new(groups).unwrap();
+            let cstr = CString::new(groups)?;
             settings.p_groups = cstr.into_raw();
         }
 
         if let Some(extra_ips) = self.extra_ips {
-            let cstr = CString::new(extra_ips).unwrap();
+            let cstr = CString::new(extra_ips)?;
             settings.p_extra_ips = cstr.into_raw();
         }
 
@@ -87,6 +92,7 @@ impl FindBuilder {
 /// messages.)
 pub struct Find {
     p_instance: Arc<OnDrop<NDIlib_find_instance_t>>,
+    guard: Arc<Mutex<()>>,
 }
 
 unsafe impl core::marker::Send for Find {}
@@ -94,58 +100,173 @@ unsafe impl core::marker::Sync for Find {}
 
 impl Find {
     /// Create a new instance with default constructor
-    pub fn new() -> Result<Self, FindCreateError> {
+    pub fn new() -> Result<Self, Error> {
+        let runtime = NdiRuntime::initialize()?;
         let p_instance = unsafe { NDIlib_find_create_v2(null()) };
         if p_instance.is_null() {
-            return Err(FindCreateError);
+            return Err(Error::CreateFailed("Find"));
         };
 
-        let p_instance = Arc::new(OnDrop::new(p_instance, |s| unsafe {
-            NDIlib_find_destroy(s)
-        }));
-        Ok(Self { p_instance })
+        let p_instance = Arc::new(
+            OnDrop::new(p_instance, |s| unsafe { NDIlib_find_destroy(s) }).with_runtime(runtime),
+        );
+        Ok(Self {
+            p_instance,
+            guard: Arc::new(Mutex::new(())),
+        })
     }
 
-    fn with_settings(settings: NDIlib_find_create_t) -> Result<Self, FindCreateError> {
+    fn with_settings(settings: NDIlib_find_create_t) -> Result<Self, Error> {
+        let runtime = NdiRuntime::initialize()?;
         let p_instance = unsafe { NDIlib_find_create_v2(&settings) };
         if p_instance.is_null() {
-            return Err(FindCreateError);
+            return Err(Error::CreateFailed("Find"));
         };
 
-        let p_instance = Arc::new(OnDrop::new(p_instance, |s| unsafe {
-            NDIlib_find_destroy(s)
-        }));
-        Ok(Self { p_instance })
+        let p_instance = Arc::new(
+            OnDrop::new(p_instance, |s| unsafe { NDIlib_find_destroy(s) }).with_runtime(runtime),
+        );
+        Ok(Self {
+            p_instance,
+            guard: Arc::new(Mutex::new(())),
+        })
     }
 
-    /// List current sources
-    pub fn current_sources(&self, timeout_ms: u128) -> Result<Vec<Source>, FindSourcesTimeout> {
-        let mut no_sources = 0;
-        let start = Instant::now();
-        let p_sources = loop {
-            // timeout if it takes an unreasonable amount of time
-            if start.elapsed().as_millis() > timeout_ms {
-                return Err(FindSourcesTimeout);
+    /// List current sources, waiting up to `timeout` for at least one source to be found
+    ///
+    /// Returns [`Error::Timeout`] if no source was found within the timeout.
+    pub fn current_sources(&self, timeout: Duration) -> Result<Vec<Source>, Error> {
+        let deadline = Instant::now() + timeout;
+        loop {
+            let sources = self.sources();
+            if !sources.is_empty() {
+                return Ok(sources);
             }
 
-            let p_sources =
-                unsafe { NDIlib_find_get_current_sources(**self.p_instance, &mut no_sources) };
-
-            if no_sources != 0 {
-                break p_sources;
-            } else {
-                yield_now();
+            let remaining = deadline.saturating_duration_since(Instant::now());
+            if remaining.is_zero() {
+                return Err(Error::Timeout);
             }
-        };
+            self.wait_for_change(remaining);
+        }
+    }
+
+    /// List the sources available right now
+    ///
+    /// Unlike [`Find::current_sources()`] this returns immediately, and the list may be empty.
+    /// The returned sources own a copy of their name and address, so they stay valid independently of this [`Find`].
+    pub fn sources(&self) -> Vec<Source> {
+        let _lock = self.guard.lock().unwrap_or_else(PoisonError::into_inner);
+        let mut no_sources = 0;
+        let p_sources =
+            unsafe { NDIlib_find_get_current_sources(**self.p_instance, &mut no_sources) };
+
+        (0..no_sources)
+            .map(|k| Source::from_binding_owned(unsafe { *p_sources.offset(k as _) }))
+            .collect()
+    }
 
-        let mut sources: Vec<Source> = vec![];
-        for k in 0..no_sources {
-            let parent = SourceParent::Find(Arc::clone(&self.p_instance));
-            sources.push(Source::from_binding(parent, unsafe {
-                *p_sources.offset(k as _)
-            }));
+    /// Wait until the list of sources on the network has changed
+    ///
+    /// the return value is whether the sources changed before the timeout
+    pub fn wait_for_change(&self, timeout: Duration) -> bool {
+        unsafe { NDIlib_find_wait_for_sources(**self.p_instance, timeout_ms(timeout)) }
+    }
+
+    /// Create a [`SourceWatcher`] which reports sources being added and removed over time
+    pub fn watch(&self) -> SourceWatcher<'_> {
+        SourceWatcher::new(self)
+    }
+
+    /// Another handle to the same finder, which shares its lock
+    #[cfg(feature = "async")]
+    pub(crate) fn share(&self) -> Self {
+        Self {
+            p_instance: Arc::clone(&self.p_instance),
+            guard: Arc::clone(&self.guard),
         }
+    }
+}
+
+/// A change of the sources available on the network
+#[derive(Debug, Clone)]
+pub enum SourceEvent {
+    /// A new source appeared on the network
+    Added(Source),
+    /// A source went offline
+    Removed(Source),
+}
 
-        Ok(sources)
+/// Tracks the sources found by a [`Find`] and reports the differences as [`SourceEvent`]s
+///
+/// The first poll reports all sources that are already available as added.
+/// Iterating over the watcher blocks until the next change occurs and never ends.
+pub struct SourceWatcher<'a> {
+    find: &'a Find,
+    known: Vec<Source>,
+    pending: VecDeque<SourceEvent>,
+    started: bool,
+}
+
+impl<'a> SourceWatcher<'a> {
+    fn new(find: &'a Find) -> Self {
+        Self {
+            find,
+            known: vec![],
+            pending: VecDeque::new(),
+            started: false,
+        }
+    }
+
+    /// The sources currently known to this watcher
+    pub fn known_sources(&self) -> &[Source] {
+        &self.known
+    }
+
+    /// Wait up to `timeout` for the sources to change and return what changed
+    ///
+    /// Returns an empty list if nothing changed within the timeout.
+    pub fn poll(&mut self, timeout: Duration) -> Vec<SourceEvent> {
+        if self.started && !self.find.wait_for_change(timeout) {
+            return vec![];
+        }
+        self.started = true;
+
+        let sources = self.find.sources();
+        let names: Vec<String> = sources.iter().map(Source::get_name).collect();
+
+        let known_names: Vec<String> = self.known.iter().map(Source::get_name).collect();
+
+        let mut events: Vec<SourceEvent> = self
+            .known
+            .iter()
+            .filter(|source| !names.contains(&source.get_name()))
+            .cloned()
+            .map(SourceEvent::Removed)
+            .collect();
+        events.extend(
+            sources
+                .iter()
+                .filter(|source| !known_names.contains(&source.get_name()))
+                .cloned()
+                .map(SourceEvent::Added),
+        );
+
+        self.known = sources;
+        events
+    }
+}
+
+impl<'a> Iterator for SourceWatcher<'a> {
+    type Item = SourceEvent;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        loop {
+            if let Some(event) = self.pending.pop_front() {
+                return Some(event);
+            }
+            let events = self.poll(Duration::from_secs(1));
+            self.pending.extend(events);
+        }
     }
 }
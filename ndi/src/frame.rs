@@ -0,0 +1,1023 @@
+use super::*;
+use std::marker::PhantomData;
+
+/// A video frame which borrows its pixels from a buffer owned by the caller
+///
+/// The frame can't outlive the buffer or metadata it was created from, which makes it
+/// safe to hand to [`Send::send_video()`].
+/// Any [`VideoData`] can be borrowed as a frame as well.
+#[derive(Clone, Copy)]
+pub struct VideoFrameRef<'a> {
+    pub(crate) p_instance: NDIlib_video_frame_v2_t,
+    _buffer: PhantomData<&'a [u8]>,
+}
+
+unsafe impl core::marker::Send for VideoFrameRef<'_> {}
+unsafe impl core::marker::Sync for VideoFrameRef<'_> {}
+
+impl<'a> VideoFrameRef<'a> {
+    /// Create a frame from a raw memory buffer, with custom settings
+    ///
+    /// The buffer has to be laid out in the given FourCC format, with `stride` bytes between lines.
+    /// A stride of 0 defaults to the size of one pixel multiplied by the width.
+    ///
+    /// Returns [`Error::OutOfRange`] if the dimensions or stride are negative or the dimensions are 0,
+    /// and [`Error::InvalidLayout`] if the stride is smaller than one line or the buffer is smaller
+    /// than the [`VideoLayout`] of the frame.
+    pub fn from_buffer(
+        width: i32,
+        height: i32,
+        fourcc: FourCCVideoType,
+        framerate_numerator: i32,
+        framerate_denominator: i32,
+        frame_format: FrameFormatType,
+        timecode: i64,
+        stride: i32,
+        metadata: Option<&'a CStr>,
+        buffer: &'a [u8],
+    ) -> Result<Self, Error> {
+        if width <= 0 {
+            return Err(Error::OutOfRange("width", width as _));
+        }
+        if height <= 0 {
+            return Err(Error::OutOfRange("height", height as _));
+        }
+        if stride < 0 {
+            return Err(Error::OutOfRange("stride", stride as _));
+        }
+        let row_bytes = VideoLayout::bytes_per_pixel(fourcc)
+            .checked_mul(width as u32)
+            .ok_or(Error::OutOfRange("width", width as _))?;
+        if stride != 0 && (stride as u32) < row_bytes {
+            return Err(Error::InvalidLayout);
+        }
+
+        let layout = VideoLayout::new(fourcc, width as _, height as _, stride as _);
+        if buffer.len() < layout.size_in_bytes() {
+            return Err(Error::InvalidLayout);
+        }
+
+        Ok(Self {
+            p_instance: NDIlib_video_frame_v2_t {
+                xres: width,
+                yres: height,
+                FourCC: fourcc as _,
+                frame_rate_N: framerate_numerator,
+                frame_rate_D: framerate_denominator,
+                picture_aspect_ratio: width as f32 / height as f32,
+                frame_format_type: frame_format as _,
+                timecode,
+                // the SDK only reads from the buffer when sending
+                p_data: buffer.as_ptr() as _,
+                __bindgen_anon_1: NDIlib_video_frame_v2_t__bindgen_ty_1 {
+                    line_stride_in_bytes: stride,
+                },
+                p_metadata: metadata.map_or(null(), |metadata| metadata.as_ptr()),
+                timestamp: NDIlib_recv_timestamp_undefined,
+            },
+            _buffer: PhantomData,
+        })
+    }
+
+    /// The width of the frame expressed in pixels.
+    pub fn width(&self) -> u32 {
+        self.p_instance.xres as _
+    }
+
+    /// The height of the frame expressed in pixels.
+    pub fn height(&self) -> u32 {
+        self.p_instance.yres as _
+    }
+
+    /// The timecode of this frame in 100 ns intervals.
+    pub fn timecode(&self) -> i64 {
+        self.p_instance.timecode
+    }
+}
+
+impl<'a> From<&'a VideoData> for VideoFrameRef<'a> {
+    fn from(video_data: &'a VideoData) -> Self {
+        Self {
+            p_instance: video_data.p_instance,
+            _buffer: PhantomData,
+        }
+    }
+}
+
+impl Debug for VideoFrameRef<'_> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("VideoFrameRef")
+            .field("width", &self.width())
+            .field("height", &self.height())
+            .field("fourcc", &self.p_instance.FourCC)
+            .field("timecode", &self.timecode())
+            .finish()
+    }
+}
+
+/// An audio frame which borrows its planar samples from a buffer owned by the caller
+///
+/// The frame can't outlive the buffer or metadata it was created from, which makes it
+/// safe to hand to [`Send::send_audio()`]. Any [`AudioData`] can be borrowed as a frame as well.
+#[derive(Clone, Copy)]
+pub struct AudioFrameRef<'a> {
+    pub(crate) p_instance: NDIlib_audio_frame_v3_t,
+    _buffer: PhantomData<&'a [f32]>,
+}
+
+unsafe impl core::marker::Send for AudioFrameRef<'_> {}
+unsafe impl core::marker::Sync for AudioFrameRef<'_> {}
+
+impl<'a> AudioFrameRef<'a> {
+    /// Create a frame from planar floating-point samples
+    ///
+    /// The samples of each channel are stored together, with `channel_stride_in_bytes` between the channels.
+    ///
+    /// Returns [`Error::InvalidLayout`] if the buffer is too small to hold all samples of all channels.
+    pub fn from_buffer(
+        sample_rate: i32,
+        no_channels: i32,
+        no_samples: i32,
+        timecode: i64,
+        channel_stride_in_bytes: i32,
+        metadata: Option<&'a CStr>,
+        buffer: &'a [f32],
+    ) -> Result<Self, Error> {
+        if no_channels > 0 && no_samples > 0 {
+            let last_channel = (no_channels - 1) as usize * channel_stride_in_bytes as usize;
+            let required = last_channel / std::mem::size_of::<f32>() + no_samples as usize;
+            if buffer.len() < required {
+                return Err(Error::InvalidLayout);
+            }
+        }
+
+        Ok(Self {
+            p_instance: NDIlib_audio_frame_v3_t {
+                sample_rate,
+                no_channels,
+                no_samples,
+                timecode,
+                FourCC: FourCCAudioType::FLTP as _,
+                // the SDK only reads from the buffer when sending
+                p_data: buffer.as_ptr() as _,
+                __bindgen_anon_1: NDIlib_audio_frame_v3_t__bindgen_ty_1 {
+                    channel_stride_in_bytes,
+                },
+                p_metadata: metadata.map_or(null(), |metadata| metadata.as_ptr()),
+                timestamp: NDIlib_recv_timestamp_undefined,
+            },
+            _buffer: PhantomData,
+        })
+    }
+
+    /// The sample-rate of this frame
+    pub fn sample_rate(&self) -> u32 {
+        self.p_instance.sample_rate as _
+    }
+
+    /// The number of audio channels
+    pub fn no_channels(&self) -> u32 {
+        self.p_instance.no_channels as _
+    }
+
+    /// The number of audio samples per channel
+    pub fn no_samples(&self) -> u32 {
+        self.p_instance.no_samples as _
+    }
+
+    /// The timecode of this frame in 100ns intervals
+    pub fn timecode(&self) -> i64 {
+        self.p_instance.timecode
+    }
+}
+
+impl<'a> From<&'a AudioData> for AudioFrameRef<'a> {
+    fn from(audio_data: &'a AudioData) -> Self {
+        Self {
+            p_instance: audio_data.p_instance,
+            _buffer: PhantomData,
+        }
+    }
+}
+
+impl Debug for AudioFrameRef<'_> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("AudioFrameRef")
+            .field("sample_rate", &self.sample_rate())
+            .field("no_channels", &self.no_channels())
+            .field("no_samples", &self.no_samples())
+            .field("timecode", &self.timecode())
+            .finish()
+    }
+}
+
+/// A single plane of a video frame, as described by [`VideoLayout`]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct VideoPlane {
+    /// The offset of the first line from the start of the frame in bytes
+    pub offset: usize,
+    /// The number of bytes between the start of two lines
+    pub line_stride_in_bytes: usize,
+    /// The number of bytes of actual data in each line, without padding
+    pub row_size_in_bytes: usize,
+    /// The number of lines in the plane
+    pub rows: usize,
+}
+
+impl VideoPlane {
+    /// The number of bytes the plane occupies in the frame
+    pub fn size_in_bytes(&self) -> usize {
+        self.line_stride_in_bytes * self.rows
+    }
+
+    /// The data of this plane within the whole frame
+    ///
+    /// Returns `None` if `frame` is too small to hold the plane.
+    pub fn slice<'a>(&self, frame: &'a [u8]) -> Option<&'a [u8]> {
+        frame.get(self.offset..self.offset + self.size_in_bytes())
+    }
+
+    /// The mutable data of this plane within the whole frame
+    ///
+    /// Returns `None` if `frame` is too small to hold the plane.
+    pub fn slice_mut<'a>(&self, frame: &'a mut [u8]) -> Option<&'a mut [u8]> {
+        frame.get_mut(self.offset..self.offset + self.size_in_bytes())
+    }
+
+    /// The lines of this plane within the whole frame, without the padding at the end of each line
+    pub fn rows<'a>(&self, frame: &'a [u8]) -> Rows<'a> {
+        Rows {
+            data: self.slice(frame).unwrap_or(&[]),
+            line_stride_in_bytes: self.line_stride_in_bytes,
+            row_size_in_bytes: self.row_size_in_bytes,
+        }
+    }
+}
+
+/// Iterator over the lines of a [`VideoPlane`]
+#[derive(Debug, Clone)]
+pub struct Rows<'a> {
+    data: &'a [u8],
+    line_stride_in_bytes: usize,
+    row_size_in_bytes: usize,
+}
+
+impl Rows<'_> {
+    pub(crate) fn empty() -> Self {
+        Self {
+            data: &[],
+            line_stride_in_bytes: 0,
+            row_size_in_bytes: 0,
+        }
+    }
+}
+
+impl<'a> Iterator for Rows<'a> {
+    type Item = &'a [u8];
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.row_size_in_bytes == 0 || self.data.len() < self.row_size_in_bytes {
+            return None;
+        }
+
+        let row = &self.data[..self.row_size_in_bytes];
+        let next = self.line_stride_in_bytes.min(self.data.len());
+        self.data = &self.data[next..];
+        Some(row)
+    }
+}
+
+/// The memory layout of an uncompressed video frame
+///
+/// The planes are derived from the FourCC as documented on [`FourCCVideoType`],
+/// in the order they appear in memory.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct VideoLayout {
+    /// The FourCC the frame is stored in
+    pub four_cc: FourCCVideoType,
+    /// The width of the frame in pixels
+    pub width: u32,
+    /// The height of the frame in pixels
+    pub height: u32,
+    /// The number of bytes between the start of two lines of the first plane
+    pub line_stride_in_bytes: u32,
+}
+
+impl VideoLayout {
+    /// Describe a frame, a `line_stride_in_bytes` of 0 means that the lines are tightly packed
+    pub fn new(
+        four_cc: FourCCVideoType,
+        width: u32,
+        height: u32,
+        line_stride_in_bytes: u32,
+    ) -> Self {
+        let line_stride_in_bytes = if line_stride_in_bytes == 0 {
+            Self::bytes_per_pixel(four_cc) * width
+        } else {
+            line_stride_in_bytes
+        };
+
+        Self {
+            four_cc,
+            width,
+            height,
+            line_stride_in_bytes,
+        }
+    }
+
+    /// The size of one pixel in the first plane, which is what the SDK assumes for a stride of 0
+    fn bytes_per_pixel(four_cc: FourCCVideoType) -> u32 {
+        match four_cc {
+            FourCCVideoType::UYVY
+            | FourCCVideoType::UYVA
+            | FourCCVideoType::P216
+            | FourCCVideoType::PA16 => 2,
+            FourCCVideoType::YV12 | FourCCVideoType::I420 | FourCCVideoType::NV12 => 1,
+            FourCCVideoType::BGRA
+            | FourCCVideoType::BGRX
+            | FourCCVideoType::RGBA
+            | FourCCVideoType::RGBX => 4,
+        }
+    }
+
+    /// The planes of the frame in memory order
+    pub fn planes(&self) -> Vec<VideoPlane> {
+        let width = self.width as usize;
+        let height = self.height as usize;
+        let stride = self.line_stride_in_bytes as usize;
+        let row_size = Self::bytes_per_pixel(self.four_cc) as usize * width;
+        let plane = |offset, line_stride_in_bytes, row_size_in_bytes, rows| VideoPlane {
+            offset,
+            line_stride_in_bytes,
+            row_size_in_bytes,
+            rows,
+        };
+        let first = plane(0, stride, row_size, height);
+        let end = stride * height;
+
+        match self.four_cc {
+            FourCCVideoType::UYVY
+            | FourCCVideoType::BGRA
+            | FourCCVideoType::BGRX
+            | FourCCVideoType::RGBA
+            | FourCCVideoType::RGBX => vec![first],
+            // the alpha plane is always tightly packed
+            FourCCVideoType::UYVA => vec![first, plane(end, width, width, height)],
+            FourCCVideoType::P216 => vec![first, plane(end, stride, row_size, height)],
+            FourCCVideoType::PA16 => vec![
+                first,
+                plane(end, stride, row_size, height),
+                plane(end * 2, stride, row_size, height),
+            ],
+            FourCCVideoType::YV12 | FourCCVideoType::I420 => {
+                // odd dimensions round up, so the last chroma row and column aren't cut off
+                let chroma = plane(
+                    end,
+                    stride.div_ceil(2),
+                    width.div_ceil(2),
+                    height.div_ceil(2),
+                );
+                let second = chroma.offset + chroma.size_in_bytes();
+                vec![
+                    first,
+                    chroma,
+                    VideoPlane {
+                        offset: second,
+                        ..chroma
+                    },
+                ]
+            }
+            FourCCVideoType::NV12 => {
+                vec![first, plane(end, stride, row_size, height.div_ceil(2))]
+            }
+        }
+    }
+
+    /// The total size of the frame in bytes, including all planes
+    pub fn size_in_bytes(&self) -> usize {
+        self.planes()
+            .last()
+            .map_or(0, |plane| plane.offset + plane.size_in_bytes())
+    }
+}
+
+fn metadata_ptr(metadata: &Option<CString>) -> *const std::os::raw::c_char {
+    metadata
+        .as_ref()
+        .map_or(null(), |metadata| metadata.as_ptr())
+}
+
+/// A video frame which owns a copy of its pixels
+///
+/// Unlike a [`VideoData`] it doesn't have to be freed back to the [`Recv`] it was captured from,
+/// so it can be cloned, stored, edited and sent with [`Send::send_video()`] at any later point.
+/// It is also what [`Send::send_video_async()`] takes, as the sender has to keep the pixels alive.
+#[derive(Debug, Clone)]
+pub struct OwnedVideoFrame {
+    width: u32,
+    height: u32,
+    four_cc: FourCCVideoType,
+    frame_rate_n: u32,
+    frame_rate_d: u32,
+    picture_aspect_ratio: f32,
+    frame_format_type: FrameFormatType,
+    timecode: i64,
+    line_stride_in_bytes: u32,
+    metadata: Option<CString>,
+    timestamp: Option<i64>,
+    data: Vec<u8>,
+}
+
+impl OwnedVideoFrame {
+    /// Create a black frame with tightly packed lines
+    pub fn new(
+        width: u32,
+        height: u32,
+        four_cc: FourCCVideoType,
+        frame_rate_n: u32,
+        frame_rate_d: u32,
+        frame_format_type: FrameFormatType,
+    ) -> Self {
+        let layout = VideoLayout::new(four_cc, width, height, 0);
+        Self {
+            width,
+            height,
+            four_cc,
+            frame_rate_n,
+            frame_rate_d,
+            picture_aspect_ratio: 0.0,
+            frame_format_type,
+            timecode: NDIlib_send_timecode_synthesize,
+            line_stride_in_bytes: layout.line_stride_in_bytes,
+            metadata: None,
+            timestamp: None,
+            data: vec![0; layout.size_in_bytes()],
+        }
+    }
+
+    /// Copy a frame into memory owned by the new frame
+    pub(crate) fn from_video_data(video_data: &VideoData) -> Result<Self, Error> {
+        let four_cc = video_data.four_cc()?;
+        let layout = video_data.layout().unwrap_or_else(|| {
+            VideoLayout::new(four_cc, video_data.width(), video_data.height(), 0)
+        });
+        let data = video_data.data().ok_or(Error::NoData)?;
+        if data.len() < layout.size_in_bytes() {
+            return Err(Error::InvalidLayout);
+        }
+        let data = data.to_vec();
+
+        let p_metadata = video_data.p_instance.p_metadata;
+        let metadata = if p_metadata.is_null() {
+            None
+        } else {
+            Some(unsafe { CStr::from_ptr(p_metadata) }.to_owned())
+        };
+
+        Ok(Self {
+            width: video_data.width(),
+            height: video_data.height(),
+            four_cc,
+            frame_rate_n: video_data.frame_rate_n(),
+            frame_rate_d: video_data.frame_rate_d(),
+            picture_aspect_ratio: video_data.picture_aspect_ratio(),
+            frame_format_type: video_data.frame_format_type()?,
+            timecode: video_data.timecode(),
+            line_stride_in_bytes: layout.line_stride_in_bytes,
+            metadata,
+            timestamp: video_data.timestamp(),
+            data,
+        })
+    }
+
+    /// The width of the frame expressed in pixels.
+    pub fn width(&self) -> u32 {
+        self.width
+    }
+
+    /// The height of the frame expressed in pixels.
+    pub fn height(&self) -> u32 {
+        self.height
+    }
+
+    /// The FourCC pixel format for this buffer.
+    pub fn four_cc(&self) -> FourCCVideoType {
+        self.four_cc
+    }
+
+    /// The numerator of the framerate of the frame.
+    pub fn frame_rate_n(&self) -> u32 {
+        self.frame_rate_n
+    }
+
+    /// The denominator of the framerate of the frame.
+    pub fn frame_rate_d(&self) -> u32 {
+        self.frame_rate_d
+    }
+
+    /// Set the framerate as a numerator and denominator
+    pub fn set_frame_rate(&mut self, frame_rate_n: u32, frame_rate_d: u32) {
+        self.frame_rate_n = frame_rate_n;
+        self.frame_rate_d = frame_rate_d;
+    }
+
+    /// The picture aspect ratio, 0.0 is interpreted as square pixels.
+    pub fn picture_aspect_ratio(&self) -> f32 {
+        self.picture_aspect_ratio
+    }
+
+    /// Set the picture aspect ratio, 0.0 is interpreted as square pixels.
+    pub fn set_picture_aspect_ratio(&mut self, picture_aspect_ratio: f32) {
+        self.picture_aspect_ratio = picture_aspect_ratio;
+    }
+
+    /// The frame format type of the frame
+    pub fn frame_format_type(&self) -> FrameFormatType {
+        self.frame_format_type
+    }
+
+    /// Set the frame format type of the frame
+    pub fn set_frame_format_type(&mut self, frame_format_type: FrameFormatType) {
+        self.frame_format_type = frame_format_type;
+    }
+
+    /// The timecode of this frame in 100 ns intervals.
+    pub fn timecode(&self) -> i64 {
+        self.timecode
+    }
+
+    /// Set the timecode of this frame in 100 ns intervals.
+    pub fn set_timecode(&mut self, timecode: i64) {
+        self.timecode = timecode;
+    }
+
+    /// This is the inter-line stride of the video data, in bytes.
+    pub fn line_stride_in_bytes(&self) -> u32 {
+        self.line_stride_in_bytes
+    }
+
+    /// The per frame metadata, which should be XML
+    pub fn metadata(&self) -> Option<&CStr> {
+        self.metadata.as_deref()
+    }
+
+    /// Set the per frame metadata, which should be XML
+    pub fn set_metadata(&mut self, metadata: Option<&str>) -> Result<(), Error> {
+        self.metadata = metadata.map(CString::new).transpose()?;
+        Ok(())
+    }
+
+    /// The timestamp the frame was originally submitted with, if it was received
+    pub fn timestamp(&self) -> Option<i64> {
+        self.timestamp
+    }
+
+    /// The memory layout of the frame
+    pub fn layout(&self) -> VideoLayout {
+        VideoLayout::new(
+            self.four_cc,
+            self.width,
+            self.height,
+            self.line_stride_in_bytes,
+        )
+    }
+
+    /// The data of each plane in memory order, see [`VideoLayout::planes()`]
+    pub fn planes(&self) -> Vec<&[u8]> {
+        self.layout()
+            .planes()
+            .iter()
+            .filter_map(|plane| plane.slice(&self.data))
+            .collect()
+    }
+
+    /// The lines of the given plane, without the padding at the end of each line
+    ///
+    /// Yields nothing if the plane doesn't exist.
+    pub fn rows(&self, plane: usize) -> Rows<'_> {
+        let layout = self.layout();
+        let plane = layout.planes().get(plane).copied();
+        plane.map_or_else(Rows::empty, |plane| plane.rows(&self.data))
+    }
+
+    /// The pixels of all planes, laid out as described by [`FourCCVideoType`]
+    pub fn data(&self) -> &[u8] {
+        &self.data
+    }
+
+    /// The pixels of all planes, laid out as described by [`FourCCVideoType`]
+    pub fn data_mut(&mut self) -> &mut [u8] {
+        &mut self.data
+    }
+}
+
+impl<'a> From<&'a OwnedVideoFrame> for VideoFrameRef<'a> {
+    fn from(frame: &'a OwnedVideoFrame) -> Self {
+        // the SDK reads the whole frame, so the buffer must never be smaller than the layout
+        assert!(frame.data.len() >= frame.layout().size_in_bytes());
+        Self {
+            p_instance: NDIlib_video_frame_v2_t {
+                xres: frame.width as _,
+                yres: frame.height as _,
+                FourCC: frame.four_cc as _,
+                frame_rate_N: frame.frame_rate_n as _,
+                frame_rate_D: frame.frame_rate_d as _,
+                picture_aspect_ratio: frame.picture_aspect_ratio,
+                frame_format_type: frame.frame_format_type as _,
+                timecode: frame.timecode,
+                p_data: frame.data.as_ptr() as _,
+                __bindgen_anon_1: NDIlib_video_frame_v2_t__bindgen_ty_1 {
+                    line_stride_in_bytes: frame.line_stride_in_bytes as _,
+                },
+                p_metadata: metadata_ptr(&frame.metadata),
+                timestamp: frame.timestamp.unwrap_or(NDIlib_recv_timestamp_undefined),
+            },
+            _buffer: PhantomData,
+        }
+    }
+}
+
+/// An audio frame which owns a copy of its samples
+///
+/// The samples are stored in planar floating-point format with the channels packed
+/// back to back. Unlike an [`AudioData`] it doesn't have to be freed back to the [`Recv`]
+/// it was captured from, so it can be cloned, stored, edited and sent with [`Send::send_audio()`] at any later point.
+#[derive(Debug, Clone)]
+pub struct OwnedAudioFrame {
+    sample_rate: u32,
+    no_channels: u32,
+    no_samples: u32,
+    timecode: i64,
+    metadata: Option<CString>,
+    timestamp: Option<i64>,
+    data: Vec<f32>,
+}
+
+impl OwnedAudioFrame {
+    /// Create a silent frame
+    pub fn new(sample_rate: u32, no_channels: u32, no_samples: u32) -> Self {
+        Self {
+            sample_rate,
+            no_channels,
+            no_samples,
+            timecode: NDIlib_send_timecode_synthesize,
+            metadata: None,
+            timestamp: None,
+            data: vec![0.0; no_channels as usize * no_samples as usize],
+        }
+    }
+
+    /// Copy a frame into memory owned by the new frame
+    pub(crate) fn from_audio_data(audio_data: &AudioData) -> Self {
+        let mut data = Vec::new();
+        let mut no_channels = 0;
+        if let Ok(channels) = audio_data.channels() {
+            for channel in channels {
+                data.extend_from_slice(channel);
+                no_channels += 1;
+            }
+        }
+
+        let p_metadata = audio_data.p_instance.p_metadata;
+        let metadata = if p_metadata.is_null() {
+            None
+        } else {
+            Some(unsafe { CStr::from_ptr(p_metadata) }.to_owned())
+        };
+
+        Self {
+            sample_rate: audio_data.sample_rate(),
+            no_channels,
+            no_samples: if no_channels == 0 {
+                0
+            } else {
+                audio_data.no_samples()
+            },
+            timecode: audio_data.timecode(),
+            metadata,
+            timestamp: audio_data.timestamp(),
+            data,
+        }
+    }
+
+    /// The sample-rate of this frame
+    pub fn sample_rate(&self) -> u32 {
+        self.sample_rate
+    }
+
+    /// Set the sample-rate of this frame
+    pub fn set_sample_rate(&mut self, sample_rate: u32) {
+        self.sample_rate = sample_rate;
+    }
+
+    /// The number of audio channels
+    pub fn no_channels(&self) -> u32 {
+        self.no_channels
+    }
+
+    /// The number of audio samples per channel
+    pub fn no_samples(&self) -> u32 {
+        self.no_samples
+    }
+
+    /// The timecode of this frame in 100ns intervals
+    pub fn timecode(&self) -> i64 {
+        self.timecode
+    }
+
+    /// Set the timecode of this frame in 100ns intervals
+    pub fn set_timecode(&mut self, timecode: i64) {
+        self.timecode = timecode;
+    }
+
+    /// The per frame metadata, which should be XML
+    pub fn metadata(&self) -> Option<&CStr> {
+        self.metadata.as_deref()
+    }
+
+    /// Set the per frame metadata, which should be XML
+    pub fn set_metadata(&mut self, metadata: Option<&str>) -> Result<(), Error> {
+        self.metadata = metadata.map(CString::new).transpose()?;
+        Ok(())
+    }
+
+    /// The timestamp the frame was originally submitted with, if it was received
+    pub fn timestamp(&self) -> Option<i64> {
+        self.timestamp
+    }
+
+    /// The samples of a single channel, `None` if the channel doesn't exist
+    pub fn channel(&self, channel: u32) -> Option<&[f32]> {
+        if channel >= self.no_channels {
+            return None;
+        }
+        let start = channel as usize * self.no_samples as usize;
+        self.data.get(start..start + self.no_samples as usize)
+    }
+
+    /// The mutable samples of a single channel, `None` if the channel doesn't exist
+    pub fn channel_mut(&mut self, channel: u32) -> Option<&mut [f32]> {
+        if channel >= self.no_channels {
+            return None;
+        }
+        let start = channel as usize * self.no_samples as usize;
+        self.data.get_mut(start..start + self.no_samples as usize)
+    }
+
+    /// Iterate over the samples of each channel
+    pub fn channels(&self) -> impl Iterator<Item = &[f32]> {
+        // frames without samples don't have any data to split into channels
+        self.data.chunks(self.no_samples.max(1) as usize)
+    }
+
+    /// Iterate over the mutable samples of each channel
+    pub fn channels_mut(&mut self) -> impl Iterator<Item = &mut [f32]> {
+        self.data.chunks_mut(self.no_samples.max(1) as usize)
+    }
+
+    /// The samples of all channels, one channel after another
+    pub fn data(&self) -> &[f32] {
+        &self.data
+    }
+
+    /// The samples of all channels, one channel after another
+    pub fn data_mut(&mut self) -> &mut [f32] {
+        &mut self.data
+    }
+}
+
+impl<'a> From<&'a OwnedAudioFrame> for AudioFrameRef<'a> {
+    fn from(frame: &'a OwnedAudioFrame) -> Self {
+        Self {
+            p_instance: NDIlib_audio_frame_v3_t {
+                sample_rate: frame.sample_rate as _,
+                no_channels: frame.no_channels as _,
+                no_samples: frame.no_samples as _,
+                timecode: frame.timecode,
+                FourCC: FourCCAudioType::FLTP as _,
+                p_data: frame.data.as_ptr() as _,
+                __bindgen_anon_1: NDIlib_audio_frame_v3_t__bindgen_ty_1 {
+                    channel_stride_in_bytes: (frame.no_samples as usize
+                        * std::mem::size_of::<f32>())
+                        as _,
+                },
+                p_metadata: metadata_ptr(&frame.metadata),
+                timestamp: frame.timestamp.unwrap_or(NDIlib_recv_timestamp_undefined),
+            },
+            _buffer: PhantomData,
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+enum Samples {
+    Planar(Vec<f32>),
+    Interleaved(Vec<f32>),
+}
+
+/// Builder struct for [`OwnedAudioFrame`]
+///
+/// Samples can be given either in planar format, with all samples of a channel stored together,
+/// or interleaved, in which case they are converted to planar. Integer samples can be converted
+/// with [`InterleavedAudio`] instead.
+#[derive(Debug, Clone)]
+pub struct AudioFrameBuilder {
+    sample_rate: u32,
+    no_channels: u32,
+    samples: Samples,
+    timecode: i64,
+    metadata: Option<String>,
+}
+
+impl AudioFrameBuilder {
+    /// Create new builder instance
+    pub fn new(sample_rate: u32, no_channels: u32) -> Self {
+        Self {
+            sample_rate,
+            no_channels,
+            samples: Samples::Planar(Vec::new()),
+            timecode: NDIlib_send_timecode_synthesize,
+            metadata: None,
+        }
+    }
+
+    /// Use planar samples, with the samples of each channel stored one channel after another
+    pub fn planar(mut self, samples: Vec<f32>) -> Self {
+        self.samples = Samples::Planar(samples);
+        self
+    }
+
+    /// Use interleaved samples, with the samples of all channels alternating
+    pub fn interleaved(mut self, samples: Vec<f32>) -> Self {
+        self.samples = Samples::Interleaved(samples);
+        self
+    }
+
+    /// The timecode of the frame in 100 ns intervals
+    ///
+    /// default: synthesized by the SDK
+    pub fn timecode(mut self, timecode: i64) -> Self {
+        self.timecode = timecode;
+        self
+    }
+
+    /// Per frame metadata, which should be XML
+    pub fn metadata(mut self, metadata: String) -> Self {
+        self.metadata = Some(metadata);
+        self
+    }
+
+    /// Build the [`OwnedAudioFrame`] instance
+    ///
+    /// Returns [`Error::OutOfRange`] for a sample rate or channel count of 0
+    /// and [`Error::InvalidLayout`] if the channels don't hold the same number of samples.
+    pub fn build(self) -> Result<OwnedAudioFrame, Error> {
+        if self.sample_rate == 0 {
+            return Err(Error::OutOfRange("sample_rate", 0.0));
+        }
+        if self.no_channels == 0 {
+            return Err(Error::OutOfRange("no_channels", 0.0));
+        }
+
+        let no_channels = self.no_channels as usize;
+        let len = match &self.samples {
+            Samples::Planar(samples) | Samples::Interleaved(samples) => samples.len(),
+        };
+        if len % no_channels != 0 {
+            return Err(Error::InvalidLayout);
+        }
+        let no_samples = len / no_channels;
+
+        let data = match self.samples {
+            Samples::Planar(samples) => samples,
+            Samples::Interleaved(samples) => {
+                let mut planar = vec![0.0; len];
+                for (i, sample) in samples.into_iter().enumerate() {
+                    planar[(i % no_channels) * no_samples + i / no_channels] = sample;
+                }
+                planar
+            }
+        };
+
+        let metadata = self.metadata.map(CString::new).transpose()?;
+
+        Ok(OwnedAudioFrame {
+            sample_rate: self.sample_rate,
+            no_channels: self.no_channels,
+            no_samples: no_samples as _,
+            timecode: self.timecode,
+            metadata,
+            timestamp: None,
+            data,
+        })
+    }
+}
+
+#[test]
+fn owned_video_frame_size() {
+    let frame = OwnedVideoFrame::new(
+        1920,
+        1080,
+        FourCCVideoType::NV12,
+        30,
+        1,
+        FrameFormatType::Progressive,
+    );
+    assert_eq!(frame.line_stride_in_bytes(), 1920);
+    assert_eq!(frame.data().len(), 1920 * 1080 * 3 / 2);
+
+    let frame = OwnedVideoFrame::new(
+        1920,
+        1080,
+        FourCCVideoType::UYVA,
+        30,
+        1,
+        FrameFormatType::Progressive,
+    );
+    assert_eq!(frame.data().len(), 1920 * 2 * 1080 + 1920 * 1080);
+}
+
+#[test]
+fn video_layout_planes() {
+    let layout = VideoLayout::new(FourCCVideoType::I420, 64, 32, 128);
+    let planes = layout.planes();
+    assert_eq!(planes.len(), 3);
+    assert_eq!(planes[1].offset, 128 * 32);
+    assert_eq!(planes[2].offset, 128 * 32 + 64 * 16);
+    assert_eq!(planes[2].row_size_in_bytes, 32);
+    assert_eq!(layout.size_in_bytes(), 128 * 32 + 2 * 64 * 16);
+
+    let data = vec![0u8; layout.size_in_bytes()];
+    let rows: Vec<_> = planes[0].rows(&data).collect();
+    assert_eq!(rows.len(), 32);
+    assert!(rows.iter().all(|row| row.len() == 64));
+
+    let odd = VideoLayout::new(FourCCVideoType::I420, 5, 3, 0);
+    let planes = odd.planes();
+    assert_eq!(planes[1].row_size_in_bytes, 3);
+    assert_eq!(planes[1].rows, 2);
+    assert_eq!(odd.size_in_bytes(), 5 * 3 + 2 * 3 * 2);
+
+    let odd = VideoLayout::new(FourCCVideoType::NV12, 4, 3, 0);
+    assert_eq!(odd.size_in_bytes(), 4 * 3 + 4 * 2);
+}
+
+#[test]
+fn video_frame_ref_checks_layout() {
+    let buffer = vec![0u8; 16 * 16 * 4];
+    let frame = |width, height, stride| {
+        VideoFrameRef::from_buffer(
+            width,
+            height,
+            FourCCVideoType::BGRA,
+            30,
+            1,
+            FrameFormatType::Progressive,
+            0,
+            stride,
+            None,
+            &buffer,
+        )
+    };
+    assert!(frame(16, 16, 0).is_ok());
+    assert!(frame(16, 16, 64).is_ok());
+    assert!(matches!(frame(16, 16, 1), Err(Error::InvalidLayout)));
+    assert!(matches!(frame(16, 17, 0), Err(Error::InvalidLayout)));
+    assert!(matches!(
+        frame(0, 16, 0),
+        Err(Error::OutOfRange("width", _))
+    ));
+    assert!(matches!(
+        frame(16, -1, 0),
+        Err(Error::OutOfRange("height", _))
+    ));
+    assert!(matches!(
+        frame(16, 16, -64),
+        Err(Error::OutOfRange("stride", _))
+    ));
+}
+
+#[test]
+fn owned_audio_frame_channels() {
+    let mut frame = OwnedAudioFrame::new(48000, 2, 4);
+    frame.channel_mut(1).unwrap().copy_from_slice(&[1.0; 4]);
+    assert!(frame.channel(2).is_none());
+    let channels: Vec<_> = frame.channels().collect();
+    assert_eq!(channels, vec![&[0.0; 4][..], &[1.0; 4][..]]);
+}
+
+#[test]
+fn audio_frame_builder_interleaved() {
+    let frame = AudioFrameBuilder::new(48000, 2)
+        .interleaved(vec![0.0, 1.0, 0.5, 1.5, 0.25, 1.25])
+        .build()
+        .unwrap();
+    assert_eq!(frame.no_samples(), 3);
+    assert_eq!(frame.channel(0).unwrap(), &[0.0, 0.5, 0.25]);
+    assert_eq!(frame.channel(1).unwrap(), &[1.0, 1.5, 1.25]);
+
+    let result = AudioFrameBuilder::new(48000, 2)
+        .planar(vec![0.0; 3])
+        .build();
+    assert!(result.is_err());
+}
@@ -0,0 +1,121 @@
+use super::*;
+use std::{mem, ops::Deref};
+
+/// Owns the frame-sync instance and keeps the bound receiver alive until after it has been destroyed
+pub(crate) struct FrameSyncInstance {
+    // fields are dropped in declaration order, so the frame-sync is destroyed before the receiver
+    p_instance: OnDrop<NDIlib_framesync_instance_t>,
+    _recv: Arc<OnDrop<NDIlib_recv_instance_t>>,
+}
+
+unsafe impl core::marker::Send for FrameSyncInstance {}
+unsafe impl core::marker::Sync for FrameSyncInstance {}
+
+impl Deref for FrameSyncInstance {
+    type Target = NDIlib_framesync_instance_t;
+
+    fn deref(&self) -> &Self::Target {
+        &self.p_instance
+    }
+}
+
+/// A frame synchronizer for pulling clock-corrected frames from a [`Recv`]
+///
+/// NDI senders and receivers very rarely share the exact same clock, which over time causes audio to drift
+/// or glitch and video to jitter. The frame-sync implements a time base corrector for video and dynamically
+/// resamples audio, turning the "push" source of a [`Recv`] into a "pull" source driven by your own clock.
+///
+/// Typical uses are video playback timed to the GPU v-sync, audio playback through a sound card
+/// and mixing several inputs against a single output clock. It is not recommended for recording a single
+/// channel, since the signal will be re-clocked.
+///
+/// Once a receiver is bound to a frame-sync, video and audio should be captured through the frame-sync.
+/// The receiver can still be used for other operations (tally, PTZ, etc...).
+pub struct FrameSync {
+    p_instance: Arc<FrameSyncInstance>,
+}
+
+unsafe impl core::marker::Send for FrameSync {}
+unsafe impl core::marker::Sync for FrameSync {}
+
+impl FrameSync {
+    /// Create a frame-sync bound to the given receiver
+    pub fn new(recv: &Recv) -> Result<Self, Error> {
+        let p_instance = unsafe { NDIlib_framesync_create(**recv.p_instance) };
+        if p_instance.is_null() {
+            return Err(Error::CreateFailed("FrameSync"));
+        }
+
+        let p_instance = Arc::new(FrameSyncInstance {
+            p_instance: OnDrop::new(p_instance, |s| unsafe { NDIlib_framesync_destroy(s) }),
+            _recv: Arc::clone(&recv.p_instance),
+        });
+        Ok(Self { p_instance })
+    }
+
+    /// Pull a video frame from the frame-sync queue.
+    ///
+    /// This always returns immediately by using time-base correction, so the same frame
+    /// can be returned multiple times. The `field_type` is used to return the best
+    /// matching frame, which keeps the field ordering correct for fielded video.
+    ///
+    /// Returns `None` if no video frame has been received yet.
+    pub fn capture_video(&self, field_type: FrameFormatType) -> Option<VideoData> {
+        let mut video: mem::MaybeUninit<NDIlib_video_frame_v2_t> = mem::MaybeUninit::zeroed();
+        unsafe {
+            NDIlib_framesync_capture_video(**self.p_instance, video.as_mut_ptr(), field_type as _);
+        }
+
+        let video = VideoData::from_binding_framesync(Arc::clone(&self.p_instance), unsafe {
+            video.assume_init()
+        });
+        if video.p_data().is_null() {
+            return None;
+        }
+
+        Some(video)
+    }
+
+    /// Pull exactly `no_samples` audio samples from the frame-sync queue.
+    ///
+    /// This always returns immediately, inserting silence if no audio is currently available.
+    /// It should be called at the rate that you want audio, and the incoming signal will be
+    /// dynamically resampled to match that rate. The requested sample rate and channel count
+    /// don't need to match the incoming signal, all conversions are supported.
+    pub fn capture_audio(&self, sample_rate: u32, no_channels: u32, no_samples: u32) -> AudioData {
+        let mut audio: mem::MaybeUninit<NDIlib_audio_frame_v3_t> = mem::MaybeUninit::zeroed();
+        unsafe {
+            NDIlib_framesync_capture_audio_v2(
+                **self.p_instance,
+                audio.as_mut_ptr(),
+                sample_rate as _,
+                no_channels as _,
+                no_samples as _,
+            );
+        }
+
+        AudioData::from_binding_framesync(Arc::clone(&self.p_instance), unsafe {
+            audio.assume_init()
+        })
+    }
+
+    /// The sample rate and number of channels of the incoming audio
+    ///
+    /// Returns `None` if no audio has been received yet.
+    pub fn audio_format(&self) -> Option<(u32, u32)> {
+        let audio = self.capture_audio(0, 0, 0);
+        if audio.sample_rate() == 0 || audio.no_channels() == 0 {
+            return None;
+        }
+
+        Some((audio.sample_rate(), audio.no_channels()))
+    }
+
+    /// The approximate current depth of the audio queue in samples
+    ///
+    /// Since the frame-sync dynamically resamples audio to match the rate it is called at,
+    /// this is mainly useful if the clock driving the capture is inaccurate.
+    pub fn audio_queue_depth(&self) -> u32 {
+        unsafe { NDIlib_framesync_audio_queue_depth(**self.p_instance) as _ }
+    }
+}
@@ -0,0 +1,409 @@
+//! Dispatches the SDK functions through the `NDIlib_v5` function table of a runtime loaded library.
+//!
+//! Every function used by the crate is redefined here with the same signature as its `extern` counterpart,
+//! which shadows the linked version in the glob import below.
+
+#[cfg(target_os = "linux")]
+pub use super::bindings_linux::*;
+
+#[cfg(target_os = "macos")]
+pub use super::bindings_macos::*;
+
+use std::{
+    env,
+    ffi::{CStr, OsStr},
+    os::raw::{c_char, c_int},
+    path::PathBuf,
+    sync::OnceLock,
+};
+
+struct Runtime {
+    // the table points into the library, so it has to stay loaded for as long as the table is used
+    _library: libloading::Library,
+    api: &'static NDIlib_v5,
+}
+
+unsafe impl core::marker::Send for Runtime {}
+unsafe impl core::marker::Sync for Runtime {}
+
+static RUNTIME: OnceLock<Runtime> = OnceLock::new();
+
+fn c_str(bytes: &'static [u8]) -> &'static str {
+    CStr::from_bytes_with_nul(bytes).unwrap().to_str().unwrap()
+}
+
+/// The paths the runtime is searched at, in order of preference
+fn library_paths() -> Vec<PathBuf> {
+    let library_name = c_str(NDILIB_LIBRARY_NAME);
+    let mut paths = Vec::new();
+    if let Some(redist_folder) = env::var_os(c_str(NDILIB_REDIST_FOLDER)) {
+        paths.push(PathBuf::from(redist_folder).join(library_name));
+    }
+    // fall back to the search path of the system loader
+    paths.push(PathBuf::from(library_name));
+    paths
+}
+
+fn open(path: &OsStr) -> Result<Runtime, String> {
+    let library = unsafe { libloading::Library::new(path) }.map_err(|e| e.to_string())?;
+    let api = unsafe {
+        let load = library
+            .get::<unsafe extern "C" fn() -> *const NDIlib_v5>(b"NDIlib_v5_load\0")
+            .map_err(|e| e.to_string())?;
+        load().as_ref()
+    };
+    let api = api.ok_or_else(|| String::from("NDIlib_v5_load returned no function table"))?;
+
+    Ok(Runtime {
+        _library: library,
+        api,
+    })
+}
+
+/// Load the NDI runtime if it hasn't been loaded yet
+///
+/// The library is looked up in the directory given by the `NDI_RUNTIME_DIR_V5` environment variable
+/// first and then through the regular search path of the system.
+pub(crate) fn load() -> Result<&'static NDIlib_v5, String> {
+    if let Some(runtime) = RUNTIME.get() {
+        return Ok(runtime.api);
+    }
+
+    let mut errors = Vec::new();
+    for path in library_paths() {
+        match open(path.as_os_str()) {
+            Ok(runtime) => return Ok(RUNTIME.get_or_init(|| runtime).api),
+            Err(e) => errors.push(format!("{}: {}", path.display(), e)),
+        }
+    }
+
+    Err(format!(
+        "failed to load the NDI runtime ({})",
+        errors.join(", ")
+    ))
+}
+
+fn api() -> &'static NDIlib_v5 {
+    match load() {
+        Ok(api) => api,
+        Err(e) => panic!("{}", e),
+    }
+}
+
+macro_rules! dynamic {
+    ($($field:ident => fn $name:ident($($arg:ident: $ty:ty),* $(,)?) $(-> $ret:ty)?;)*) => {
+        $(
+            pub unsafe fn $name($($arg: $ty),*) $(-> $ret)? {
+                let function = unsafe { api().$field.$name }
+                    .expect(concat!(stringify!($name), " is missing from the loaded NDI runtime"));
+                unsafe { function($($arg),*) }
+            }
+        )*
+    };
+}
+
+dynamic! {
+    __bindgen_anon_2 => fn NDIlib_destroy();
+    __bindgen_anon_6 => fn NDIlib_find_create_v2(
+        p_create_settings: *const NDIlib_find_create_t,
+    ) -> NDIlib_find_instance_t;
+    __bindgen_anon_7 => fn NDIlib_find_destroy(p_instance: NDIlib_find_instance_t);
+    __bindgen_anon_44 => fn NDIlib_find_get_current_sources(
+        p_instance: NDIlib_find_instance_t,
+        p_no_sources: *mut u32,
+    ) -> *const NDIlib_source_t;
+    __bindgen_anon_43 => fn NDIlib_find_wait_for_sources(
+        p_instance: NDIlib_find_instance_t,
+        timeout_in_ms: u32,
+    ) -> bool;
+    __bindgen_anon_106 => fn NDIlib_framesync_audio_queue_depth(
+        p_instance: NDIlib_framesync_instance_t,
+    ) -> c_int;
+    __bindgen_anon_104 => fn NDIlib_framesync_capture_audio_v2(
+        p_instance: NDIlib_framesync_instance_t,
+        p_audio_data: *mut NDIlib_audio_frame_v3_t,
+        sample_rate: c_int,
+        no_channels: c_int,
+        no_samples: c_int,
+    );
+    __bindgen_anon_91 => fn NDIlib_framesync_capture_video(
+        p_instance: NDIlib_framesync_instance_t,
+        p_video_data: *mut NDIlib_video_frame_v2_t,
+        field_type: NDIlib_frame_format_type_e,
+    );
+    __bindgen_anon_87 => fn NDIlib_framesync_create(
+        p_receiver: NDIlib_recv_instance_t,
+    ) -> NDIlib_framesync_instance_t;
+    __bindgen_anon_88 => fn NDIlib_framesync_destroy(p_instance: NDIlib_framesync_instance_t);
+    __bindgen_anon_105 => fn NDIlib_framesync_free_audio_v2(
+        p_instance: NDIlib_framesync_instance_t,
+        p_audio_data: *mut NDIlib_audio_frame_v3_t,
+    );
+    __bindgen_anon_92 => fn NDIlib_framesync_free_video(
+        p_instance: NDIlib_framesync_instance_t,
+        p_video_data: *mut NDIlib_video_frame_v2_t,
+    );
+    __bindgen_anon_1 => fn NDIlib_initialize() -> bool;
+    __bindgen_anon_4 => fn NDIlib_is_supported_CPU() -> bool;
+    __bindgen_anon_34 => fn NDIlib_recv_add_connection_metadata(
+        p_instance: NDIlib_recv_instance_t,
+        p_metadata: *const NDIlib_metadata_frame_t,
+    );
+    __bindgen_anon_102 => fn NDIlib_recv_capture_v3(
+        p_instance: NDIlib_recv_instance_t,
+        p_video_data: *mut NDIlib_video_frame_v2_t,
+        p_audio_data: *mut NDIlib_audio_frame_v3_t,
+        p_metadata: *mut NDIlib_metadata_frame_t,
+        timeout_in_ms: u32,
+    ) -> NDIlib_frame_type_e;
+    __bindgen_anon_33 => fn NDIlib_recv_clear_connection_metadata(
+        p_instance: NDIlib_recv_instance_t,
+    );
+    __bindgen_anon_86 => fn NDIlib_recv_connect(
+        p_instance: NDIlib_recv_instance_t,
+        p_src: *const NDIlib_source_t,
+    );
+    __bindgen_anon_85 => fn NDIlib_recv_create_v3(
+        p_create_settings: *const NDIlib_recv_create_v3_t,
+    ) -> NDIlib_recv_instance_t;
+    __bindgen_anon_24 => fn NDIlib_recv_destroy(p_instance: NDIlib_recv_instance_t);
+    __bindgen_anon_103 => fn NDIlib_recv_free_audio_v3(
+        p_instance: NDIlib_recv_instance_t,
+        p_audio_data: *const NDIlib_audio_frame_v3_t,
+    );
+    __bindgen_anon_28 => fn NDIlib_recv_free_metadata(
+        p_instance: NDIlib_recv_instance_t,
+        p_metadata: *const NDIlib_metadata_frame_t,
+    );
+    __bindgen_anon_58 => fn NDIlib_recv_free_string(
+        p_instance: NDIlib_recv_instance_t,
+        p_string: *const c_char,
+    );
+    __bindgen_anon_48 => fn NDIlib_recv_free_video_v2(
+        p_instance: NDIlib_recv_instance_t,
+        p_video_data: *const NDIlib_video_frame_v2_t,
+    );
+    __bindgen_anon_35 => fn NDIlib_recv_get_no_connections(
+        p_instance: NDIlib_recv_instance_t,
+    ) -> c_int;
+    __bindgen_anon_31 => fn NDIlib_recv_get_performance(
+        p_instance: NDIlib_recv_instance_t,
+        p_total: *mut NDIlib_recv_performance_t,
+        p_dropped: *mut NDIlib_recv_performance_t,
+    );
+    __bindgen_anon_32 => fn NDIlib_recv_get_queue(
+        p_instance: NDIlib_recv_instance_t,
+        p_total: *mut NDIlib_recv_queue_t,
+    );
+    __bindgen_anon_68 => fn NDIlib_recv_ptz_auto_focus(p_instance: NDIlib_recv_instance_t) -> bool;
+    __bindgen_anon_76 => fn NDIlib_recv_ptz_exposure_auto(
+        p_instance: NDIlib_recv_instance_t,
+    ) -> bool;
+    __bindgen_anon_107 => fn NDIlib_recv_ptz_exposure_manual_v2(
+        p_instance: NDIlib_recv_instance_t,
+        iris: f32,
+        gain: f32,
+        shutter_speed: f32,
+    ) -> bool;
+    __bindgen_anon_69 => fn NDIlib_recv_ptz_focus(
+        p_instance: NDIlib_recv_instance_t,
+        focus_value: f32,
+    ) -> bool;
+    __bindgen_anon_70 => fn NDIlib_recv_ptz_focus_speed(
+        p_instance: NDIlib_recv_instance_t,
+        focus_speed: f32,
+    ) -> bool;
+    __bindgen_anon_59 => fn NDIlib_recv_ptz_is_supported(
+        p_instance: NDIlib_recv_instance_t,
+    ) -> bool;
+    __bindgen_anon_64 => fn NDIlib_recv_ptz_pan_tilt(
+        p_instance: NDIlib_recv_instance_t,
+        pan_value: f32,
+        tilt_value: f32,
+    ) -> bool;
+    __bindgen_anon_65 => fn NDIlib_recv_ptz_pan_tilt_speed(
+        p_instance: NDIlib_recv_instance_t,
+        pan_speed: f32,
+        tilt_speed: f32,
+    ) -> bool;
+    __bindgen_anon_67 => fn NDIlib_recv_ptz_recall_preset(
+        p_instance: NDIlib_recv_instance_t,
+        preset_no: c_int,
+        speed: f32,
+    ) -> bool;
+    __bindgen_anon_66 => fn NDIlib_recv_ptz_store_preset(
+        p_instance: NDIlib_recv_instance_t,
+        preset_no: c_int,
+    ) -> bool;
+    __bindgen_anon_71 => fn NDIlib_recv_ptz_white_balance_auto(
+        p_instance: NDIlib_recv_instance_t,
+    ) -> bool;
+    __bindgen_anon_72 => fn NDIlib_recv_ptz_white_balance_indoor(
+        p_instance: NDIlib_recv_instance_t,
+    ) -> bool;
+    __bindgen_anon_75 => fn NDIlib_recv_ptz_white_balance_manual(
+        p_instance: NDIlib_recv_instance_t,
+        red: f32,
+        blue: f32,
+    ) -> bool;
+    __bindgen_anon_74 => fn NDIlib_recv_ptz_white_balance_oneshot(
+        p_instance: NDIlib_recv_instance_t,
+    ) -> bool;
+    __bindgen_anon_73 => fn NDIlib_recv_ptz_white_balance_outdoor(
+        p_instance: NDIlib_recv_instance_t,
+    ) -> bool;
+    __bindgen_anon_62 => fn NDIlib_recv_ptz_zoom(
+        p_instance: NDIlib_recv_instance_t,
+        zoom_value: f32,
+    ) -> bool;
+    __bindgen_anon_63 => fn NDIlib_recv_ptz_zoom_speed(
+        p_instance: NDIlib_recv_instance_t,
+        zoom_speed: f32,
+    ) -> bool;
+    __bindgen_anon_83 => fn NDIlib_recv_recording_get_error(
+        p_instance: NDIlib_recv_instance_t,
+    ) -> *const c_char;
+    __bindgen_anon_82 => fn NDIlib_recv_recording_get_filename(
+        p_instance: NDIlib_recv_instance_t,
+    ) -> *const c_char;
+    __bindgen_anon_84 => fn NDIlib_recv_recording_get_times(
+        p_instance: NDIlib_recv_instance_t,
+        p_times: *mut NDIlib_recv_recording_time_t,
+    ) -> bool;
+    __bindgen_anon_81 => fn NDIlib_recv_recording_is_recording(
+        p_instance: NDIlib_recv_instance_t,
+    ) -> bool;
+    __bindgen_anon_60 => fn NDIlib_recv_recording_is_supported(
+        p_instance: NDIlib_recv_instance_t,
+    ) -> bool;
+    __bindgen_anon_80 => fn NDIlib_recv_recording_set_audio_level(
+        p_instance: NDIlib_recv_instance_t,
+        level_dB: f32,
+    ) -> bool;
+    __bindgen_anon_78 => fn NDIlib_recv_recording_start(
+        p_instance: NDIlib_recv_instance_t,
+        p_filename_hint: *const c_char,
+    ) -> bool;
+    __bindgen_anon_79 => fn NDIlib_recv_recording_stop(p_instance: NDIlib_recv_instance_t) -> bool;
+    __bindgen_anon_29 => fn NDIlib_recv_send_metadata(
+        p_instance: NDIlib_recv_instance_t,
+        p_metadata: *const NDIlib_metadata_frame_t,
+    ) -> bool;
+    __bindgen_anon_30 => fn NDIlib_recv_set_tally(
+        p_instance: NDIlib_recv_instance_t,
+        p_tally: *const NDIlib_tally_t,
+    ) -> bool;
+    __bindgen_anon_38 => fn NDIlib_routing_change(
+        p_instance: NDIlib_routing_instance_t,
+        p_source: *const NDIlib_source_t,
+    ) -> bool;
+    __bindgen_anon_39 => fn NDIlib_routing_clear(p_instance: NDIlib_routing_instance_t) -> bool;
+    __bindgen_anon_36 => fn NDIlib_routing_create(
+        p_create_settings: *const NDIlib_routing_create_t,
+    ) -> NDIlib_routing_instance_t;
+    __bindgen_anon_37 => fn NDIlib_routing_destroy(p_instance: NDIlib_routing_instance_t);
+    __bindgen_anon_100 => fn NDIlib_routing_get_no_connections(
+        p_instance: NDIlib_routing_instance_t,
+        timeout_in_ms: u32,
+    ) -> c_int;
+    __bindgen_anon_101 => fn NDIlib_routing_get_source_name(
+        p_instance: NDIlib_routing_instance_t,
+    ) -> *const NDIlib_source_t;
+    __bindgen_anon_20 => fn NDIlib_send_add_connection_metadata(
+        p_instance: NDIlib_send_instance_t,
+        p_metadata: *const NDIlib_metadata_frame_t,
+    );
+    __bindgen_anon_15 => fn NDIlib_send_capture(
+        p_instance: NDIlib_send_instance_t,
+        p_metadata: *mut NDIlib_metadata_frame_t,
+        timeout_in_ms: u32,
+    ) -> NDIlib_frame_type_e;
+    __bindgen_anon_19 => fn NDIlib_send_clear_connection_metadata(
+        p_instance: NDIlib_send_instance_t,
+    );
+    __bindgen_anon_9 => fn NDIlib_send_create(
+        p_create_settings: *const NDIlib_send_create_t,
+    ) -> NDIlib_send_instance_t;
+    __bindgen_anon_10 => fn NDIlib_send_destroy(p_instance: NDIlib_send_instance_t);
+    __bindgen_anon_16 => fn NDIlib_send_free_metadata(
+        p_instance: NDIlib_send_instance_t,
+        p_metadata: *const NDIlib_metadata_frame_t,
+    );
+    __bindgen_anon_18 => fn NDIlib_send_get_no_connections(
+        p_instance: NDIlib_send_instance_t,
+        timeout_in_ms: u32,
+    ) -> c_int;
+    __bindgen_anon_96 => fn NDIlib_send_get_source_name(
+        p_instance: NDIlib_send_instance_t,
+    ) -> *const NDIlib_source_t;
+    __bindgen_anon_17 => fn NDIlib_send_get_tally(
+        p_instance: NDIlib_send_instance_t,
+        p_tally: *mut NDIlib_tally_t,
+        timeout_in_ms: u32,
+    ) -> bool;
+    __bindgen_anon_97 => fn NDIlib_send_send_audio_v3(
+        p_instance: NDIlib_send_instance_t,
+        p_audio_data: *const NDIlib_audio_frame_v3_t,
+    );
+    __bindgen_anon_14 => fn NDIlib_send_send_metadata(
+        p_instance: NDIlib_send_instance_t,
+        p_metadata: *const NDIlib_metadata_frame_t,
+    );
+    __bindgen_anon_52 => fn NDIlib_send_send_video_async_v2(
+        p_instance: NDIlib_send_instance_t,
+        p_video_data: *const NDIlib_video_frame_v2_t,
+    );
+    __bindgen_anon_51 => fn NDIlib_send_send_video_v2(
+        p_instance: NDIlib_send_instance_t,
+        p_video_data: *const NDIlib_video_frame_v2_t,
+    );
+    __bindgen_anon_21 => fn NDIlib_send_set_failover(
+        p_instance: NDIlib_send_instance_t,
+        p_failover_source: *const NDIlib_source_t,
+    );
+    __bindgen_anon_99 => fn NDIlib_util_P216_to_V210(
+        p_src_p216: *const NDIlib_video_frame_v2_t,
+        p_dst_v210: *mut NDIlib_video_frame_v2_t,
+    );
+    __bindgen_anon_98 => fn NDIlib_util_V210_to_P216(
+        p_src_v210: *const NDIlib_video_frame_v2_t,
+        p_dst_p216: *mut NDIlib_video_frame_v2_t,
+    );
+    __bindgen_anon_55 => fn NDIlib_util_audio_from_interleaved_16s_v2(
+        p_src: *const NDIlib_audio_frame_interleaved_16s_t,
+        p_dst: *mut NDIlib_audio_frame_v2_t,
+    );
+    __bindgen_anon_57 => fn NDIlib_util_audio_from_interleaved_32f_v2(
+        p_src: *const NDIlib_audio_frame_interleaved_32f_t,
+        p_dst: *mut NDIlib_audio_frame_v2_t,
+    );
+    __bindgen_anon_95 => fn NDIlib_util_audio_from_interleaved_32s_v2(
+        p_src: *const NDIlib_audio_frame_interleaved_32s_t,
+        p_dst: *mut NDIlib_audio_frame_v2_t,
+    );
+    __bindgen_anon_54 => fn NDIlib_util_audio_to_interleaved_16s_v2(
+        p_src: *const NDIlib_audio_frame_v2_t,
+        p_dst: *mut NDIlib_audio_frame_interleaved_16s_t,
+    );
+    __bindgen_anon_56 => fn NDIlib_util_audio_to_interleaved_32f_v2(
+        p_src: *const NDIlib_audio_frame_v2_t,
+        p_dst: *mut NDIlib_audio_frame_interleaved_32f_t,
+    );
+    __bindgen_anon_94 => fn NDIlib_util_audio_to_interleaved_32s_v2(
+        p_src: *const NDIlib_audio_frame_v2_t,
+        p_dst: *mut NDIlib_audio_frame_interleaved_32s_t,
+    );
+    __bindgen_anon_40 => fn NDIlib_util_send_send_audio_interleaved_16s(
+        p_instance: NDIlib_send_instance_t,
+        p_audio_data: *const NDIlib_audio_frame_interleaved_16s_t,
+    );
+    __bindgen_anon_47 => fn NDIlib_util_send_send_audio_interleaved_32f(
+        p_instance: NDIlib_send_instance_t,
+        p_audio_data: *const NDIlib_audio_frame_interleaved_32f_t,
+    );
+    __bindgen_anon_93 => fn NDIlib_util_send_send_audio_interleaved_32s(
+        p_instance: NDIlib_send_instance_t,
+        p_audio_data: *const NDIlib_audio_frame_interleaved_32s_t,
+    );
+    __bindgen_anon_3 => fn NDIlib_version() -> *const c_char;
+}
@@ -4,6 +4,7 @@
 #![allow(non_snake_case)]
 #![allow(non_upper_case_globals)]
 
+use crate::NdiRuntime;
 use std::ops::{Deref, DerefMut};
 
 #[cfg(target_os = "windows")]
@@ -15,26 +16,49 @@ mod bindings_linux;
 #[cfg(target_os = "macos")]
 mod bindings_macos;
 
+#[cfg(all(feature = "dynamic-link", target_os = "windows"))]
+compile_error!(
+    "the `dynamic-link` feature requires the NDI 5 SDK, which is not bundled for Windows yet"
+);
+
+#[cfg(feature = "dynamic-link")]
+pub(crate) mod dynamic;
+
 pub mod bindings {
     #[cfg(target_os = "windows")]
     pub use super::bindings_windows::*;
 
-    #[cfg(target_os = "linux")]
+    #[cfg(all(target_os = "linux", not(feature = "dynamic-link")))]
     pub use super::bindings_linux::*;
 
-    #[cfg(target_os = "macos")]
+    #[cfg(all(target_os = "macos", not(feature = "dynamic-link")))]
     pub use super::bindings_macos::*;
+
+    #[cfg(feature = "dynamic-link")]
+    pub use super::dynamic::*;
 }
 
 /// Utility for adding a destructor function to a pointer which is called once the struct is dropped.
 pub(crate) struct OnDrop<P: Copy> {
     inner: P,
     destroy: fn(P),
+    // dropped after the destructor has run, so the library outlives the instance
+    runtime: Option<NdiRuntime>,
 }
 
 impl<T> OnDrop<*mut T> {
     pub(crate) fn new(inner: *mut T, destroy: fn(*mut T)) -> Self {
-        OnDrop { inner, destroy }
+        OnDrop {
+            inner,
+            destroy,
+            runtime: None,
+        }
+    }
+
+    /// Keep the library initialized until the pointer has been destroyed
+    pub(crate) fn with_runtime(mut self, runtime: NdiRuntime) -> Self {
+        self.runtime = Some(runtime);
+        self
     }
 }
 
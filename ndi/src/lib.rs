@@ -6,38 +6,83 @@
 //! http://ndi.tv/
 //!
 
+use framesync::FrameSyncInstance;
 use internal::{bindings::*, OnDrop};
 use std::{
     convert::TryFrom,
-    ffi::{CStr, CString},
+    ffi::{CStr, CString, NulError},
     fmt::{Debug, Display},
     ptr::{null, null_mut},
-    sync::Arc,
+    sync::{Arc, PoisonError},
+    time::Duration,
 };
 
 /// The error type used in this crate
 pub mod error;
 /// The [`Find`] struct and related constructs for finding NDI sources
 pub mod find;
+/// Borrowed and owned video and audio frames which can be sent
+pub mod frame;
+/// The [`FrameSync`] struct for capturing clock-corrected frames from a [`Recv`]
+pub mod framesync;
 #[doc(hidden)]
 pub mod internal;
+/// The [`PtzControl`] struct and related constructs for controlling PTZ cameras
+pub mod ptz;
+/// The [`Recording`] struct and related constructs for controlling remote recorders
+pub mod recording;
 /// The [`Recv`] struct and related constructs for receiving NDI
 pub mod recv;
+/// The [`Router`] struct and related constructs for routing NDI sources
+pub mod routing;
+/// The [`NdiRuntime`] handle which keeps the library initialized
+pub mod runtime;
 /// The [`Send`] struct and related constructs for sending NDI
 pub mod send;
+/// [`Stream`](futures_core::Stream)s and futures driven by capture threads, requires the `async` feature
+#[cfg(feature = "async")]
+pub mod stream;
+/// Utilities for converting audio and video between formats
+pub mod util;
+/// The [`RecvWorker`] struct for capturing frames on background threads
+pub mod worker;
 
 #[doc(hidden)]
 pub use error::*;
 #[doc(hidden)]
 pub use find::*;
 #[doc(hidden)]
+pub use frame::*;
+#[doc(hidden)]
+pub use framesync::*;
+#[doc(hidden)]
+pub use ptz::*;
+#[doc(hidden)]
+pub use recording::*;
+#[doc(hidden)]
 pub use recv::*;
 #[doc(hidden)]
+pub use routing::*;
+#[doc(hidden)]
+pub use runtime::*;
+#[doc(hidden)]
 pub use send::*;
+#[cfg(feature = "async")]
+#[doc(hidden)]
+pub use stream::*;
+#[doc(hidden)]
+pub use util::*;
+#[doc(hidden)]
+pub use worker::*;
+
+/// Convert a timeout into the milliseconds expected by the SDK, saturating at `u32::MAX`
+fn timeout_ms(timeout: Duration) -> u32 {
+    timeout.as_millis().min(u32::MAX as u128) as _
+}
 
 /// A description of the type of of frame received.
 ///
-/// This is usually returned by [`Recv::capture_all()`]
+/// This is returned by the deprecated [`Recv::capture_all()`], see [`Frame`] instead
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum FrameType {
     /// nothing changed, usually due to timeout
@@ -49,7 +94,7 @@ pub enum FrameType {
     /// Received a metadata frame
     Metadata = NDIlib_frame_type_e_NDIlib_frame_type_metadata as _,
     /// This indicates that the settings on this input have changed.
-    /// For instance, this value will be returned from [`recv::Recv::capture_all()`].
+    /// For instance, this value will be returned from [`Recv::capture_all()`]
     /// when the device is known to have new settings, for instance the web URL has changed or the device
     /// is now known to be a PTZ camera.
     StatusChange = NDIlib_frame_type_e_NDIlib_frame_type_status_change as _,
@@ -58,7 +103,7 @@ pub enum FrameType {
 }
 
 impl TryFrom<NDIlib_frame_type_e> for FrameType {
-    type Error = InvalidEnum;
+    type Error = Error;
 
     fn try_from(value: NDIlib_frame_type_e) -> Result<Self, Self::Error> {
         #[allow(non_upper_case_globals)]
@@ -69,7 +114,7 @@ impl TryFrom<NDIlib_frame_type_e> for FrameType {
             NDIlib_frame_type_e_NDIlib_frame_type_status_change => Ok(FrameType::StatusChange),
             NDIlib_frame_type_e_NDIlib_frame_type_error => Ok(FrameType::ErrorFrame),
             NDIlib_frame_type_e_NDIlib_frame_type_metadata => Ok(FrameType::Metadata),
-            x => Err(InvalidEnum(x as _, "FrameType")),
+            x => Err(Error::InvalidEnum(x as _, "FrameType")),
         }
     }
 }
@@ -109,7 +154,7 @@ pub enum FrameFormatType {
 }
 
 impl TryFrom<NDIlib_frame_format_type_e> for FrameFormatType {
-    type Error = InvalidEnum;
+    type Error = Error;
 
     fn try_from(value: NDIlib_frame_format_type_e) -> Result<Self, Self::Error> {
         #[allow(non_upper_case_globals)]
@@ -128,7 +173,7 @@ impl TryFrom<NDIlib_frame_format_type_e> for FrameFormatType {
             NDIlib_frame_format_type_e_NDIlib_frame_format_type_field_1 => {
                 Ok(FrameFormatType::Field1)
             }
-            x => Err(InvalidEnum(x as _, "FrameFormatType")),
+            x => Err(Error::InvalidEnum(x as _, "FrameFormatType")),
         }
     }
 }
@@ -245,7 +290,7 @@ pub enum FourCCVideoType {
 }
 
 impl TryFrom<NDIlib_FourCC_video_type_e> for FourCCVideoType {
-    type Error = InvalidEnum;
+    type Error = Error;
 
     fn try_from(value: NDIlib_FourCC_video_type_e) -> Result<Self, Self::Error> {
         #[allow(non_upper_case_globals)]
@@ -261,7 +306,7 @@ impl TryFrom<NDIlib_FourCC_video_type_e> for FourCCVideoType {
             NDIlib_FourCC_video_type_e_NDIlib_FourCC_type_RGBA => Ok(FourCCVideoType::RGBA),
             NDIlib_FourCC_video_type_e_NDIlib_FourCC_type_BGRX => Ok(FourCCVideoType::BGRX),
             NDIlib_FourCC_video_type_e_NDIlib_FourCC_type_RGBX => Ok(FourCCVideoType::RGBX),
-            x => Err(InvalidEnum(x as _, "FourCCVideoType")),
+            x => Err(Error::InvalidEnum(x as _, "FourCCVideoType")),
         }
     }
 }
@@ -274,21 +319,26 @@ pub enum FourCCAudioType {
 }
 
 impl TryFrom<NDIlib_FourCC_audio_type_e> for FourCCAudioType {
-    type Error = InvalidEnum;
+    type Error = Error;
 
     fn try_from(value: NDIlib_FourCC_audio_type_e) -> Result<Self, Self::Error> {
         #[allow(non_upper_case_globals)]
         match value {
             NDIlib_FourCC_audio_type_e_NDIlib_FourCC_type_FLTP => Ok(FourCCAudioType::FLTP),
-            x => Err(InvalidEnum(x as _, "FourCCAudioType")),
+            x => Err(Error::InvalidEnum(x as _, "FourCCAudioType")),
         }
     }
 }
 
 #[derive(Clone)]
 enum SourceParent {
-    Find(Arc<OnDrop<NDIlib_find_instance_t>>),
     Send(Arc<OnDrop<NDIlib_send_instance_t>>),
+    Routing(Arc<OnDrop<NDIlib_routing_instance_t>>),
+    /// The name and url address are owned by the source itself
+    ///
+    /// Only held to keep the strings alive, they are read through `p_instance`.
+    #[allow(dead_code)]
+    Owned(Arc<CString>, Option<Arc<CString>>),
     None,
 }
 
@@ -303,6 +353,7 @@ impl Debug for Source {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         f.debug_struct("ndi::Source")
             .field("name", &self.get_name())
+            .field("url_address", &self.url_address())
             .finish()
     }
 }
@@ -315,7 +366,45 @@ impl Source {
         }
     }
 
-    fn new() -> Self {
+    /// Create a source from its NDI name and an optional url address.
+    ///
+    /// This allows connecting to a known source, for instance from a saved configuration,
+    /// without waiting for it to be discovered by [`Find`].
+    /// The name is in the form `MACHINE_NAME (NDI_SOURCE_NAME)`, see [`Source::get_name()`].
+    pub fn new(ndi_name: &str, url_address: Option<&str>) -> Result<Self, Error> {
+        let ndi_name = Arc::new(CString::new(ndi_name)?);
+        let url_address = url_address.map(CString::new).transpose()?.map(Arc::new);
+        Ok(Self::from_owned(ndi_name, url_address))
+    }
+
+    fn from_owned(ndi_name: Arc<CString>, url_address: Option<Arc<CString>>) -> Self {
+        let p_instance = NDIlib_source_t {
+            p_ndi_name: ndi_name.as_ptr(),
+            __bindgen_anon_1: NDIlib_source_t__bindgen_ty_1 {
+                p_url_address: url_address.as_ref().map_or(null(), |url| url.as_ptr()),
+            },
+        };
+        Self {
+            parent: SourceParent::Owned(ndi_name, url_address),
+            p_instance,
+        }
+    }
+
+    /// Copy the strings of a source which is only valid for a limited time
+    fn from_binding_owned(source: NDIlib_source_t) -> Self {
+        let copy = |p_str: *const std::os::raw::c_char| {
+            if p_str.is_null() {
+                None
+            } else {
+                Some(Arc::new(unsafe { CStr::from_ptr(p_str) }.to_owned()))
+            }
+        };
+        let ndi_name = copy(source.p_ndi_name).unwrap_or_default();
+        let url_address = copy(unsafe { source.__bindgen_anon_1.p_url_address });
+        Self::from_owned(ndi_name, url_address)
+    }
+
+    fn empty() -> Self {
         // From the default c++ constructor in Processing.NDI.structs.h
         let p_instance = NDIlib_source_t {
             p_ndi_name: null(),
@@ -349,6 +438,21 @@ impl Source {
         };
         name
     }
+
+    /// The url address of this source, if it is known.
+    ///
+    /// This is the IP address and port number of the source, which allows connecting
+    /// to it without a name lookup.
+    pub fn url_address(&self) -> Option<String> {
+        let url_char_ptr = unsafe { self.p_instance.__bindgen_anon_1.p_url_address };
+        if url_char_ptr.is_null() {
+            return None;
+        }
+        let url_address = unsafe { CStr::from_ptr(url_char_ptr) }
+            .to_string_lossy()
+            .to_string();
+        Some(url_address)
+    }
 }
 
 unsafe impl core::marker::Send for Source {}
@@ -356,7 +460,7 @@ unsafe impl core::marker::Sync for Source {}
 
 /// Tally information
 #[repr(C)]
-#[derive(Debug, Copy, Clone)]
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
 pub struct Tally {
     /// Is this currently on program output
     pub on_program: bool,
@@ -400,6 +504,12 @@ impl Into<NDIlib_tally_t> for Tally {
 
 enum VideoParent {
     Recv(Arc<OnDrop<NDIlib_recv_instance_t>>),
+    FrameSync(Arc<FrameSyncInstance>),
+    /// The pixels are owned by the frame itself
+    ///
+    /// Only held to keep the buffer alive, it is read through `p_instance`.
+    #[allow(dead_code)]
+    Buffer(Vec<u8>),
     Owned,
 }
 
@@ -448,6 +558,24 @@ impl VideoData {
         }
     }
 
+    fn from_binding_framesync(
+        framesync: Arc<FrameSyncInstance>,
+        p_instance: NDIlib_video_frame_v2_t,
+    ) -> Self {
+        Self {
+            p_instance,
+            parent: VideoParent::FrameSync(framesync),
+        }
+    }
+
+    /// Create a frame which points into `buffer`
+    fn from_binding_buffer(p_instance: NDIlib_video_frame_v2_t, buffer: Vec<u8>) -> Self {
+        Self {
+            p_instance,
+            parent: VideoParent::Buffer(buffer),
+        }
+    }
+
     /// Create an empty video frame
     pub fn new() -> Self {
         Self {
@@ -471,46 +599,6 @@ impl VideoData {
         }
     }
 
-    /// Create VideoData from a raw memory buffer, with custom settings
-    pub fn from_buffer(
-        width: i32,
-        height: i32,
-        fourcc: FourCCVideoType,
-        framerate_numerator: i32,
-        framerate_denominator: i32,
-        frame_format: FrameFormatType,
-        timecode: i64,
-        stride: i32,
-        metadata: Option<&CStr>,
-        buffer: &mut [u8],
-    ) -> Self {
-        Self {
-            p_instance: NDIlib_video_frame_v2_t {
-                xres: width,
-                yres: height,
-                FourCC: fourcc as _,
-                frame_rate_N: framerate_numerator,
-                frame_rate_D: framerate_denominator,
-                picture_aspect_ratio: width as f32 / height as f32,
-                frame_format_type: frame_format as _,
-                timecode,
-                p_data: buffer.as_mut_ptr(),
-                // can't seem to figure out which FourCC types correspond to the "compressed format"
-                // since even for UYUV and such, it seems to still use line_stride_in_bytes rather than data_size_in_bytes
-                __bindgen_anon_1: NDIlib_video_frame_v2_t__bindgen_ty_1 {
-                    line_stride_in_bytes: stride,
-                },
-                p_metadata: if let Some(metadata) = metadata {
-                    metadata.as_ptr()
-                } else {
-                    null()
-                },
-                timestamp: NDIlib_recv_timestamp_undefined,
-            },
-            parent: VideoParent::Owned,
-        }
-    }
-
     /// The width of the frame expressed in pixels.
     ///
     /// Note that, because data is internally all considered
@@ -527,9 +615,10 @@ impl VideoData {
 
     /// The FourCC pixel format for this buffer.
     ///
-    /// See [`FourCCVideoType`] for details on possible values
-    pub fn four_cc(&self) -> FourCCVideoType {
-        FourCCVideoType::try_from(self.p_instance.FourCC).unwrap()
+    /// See [`FourCCVideoType`] for details on possible values.
+    /// Returns an error for compressed or other formats unknown to this crate.
+    pub fn four_cc(&self) -> Result<FourCCVideoType, Error> {
+        FourCCVideoType::try_from(self.p_instance.FourCC)
     }
 
     /// The numerator of the framerate of the current frame.
@@ -573,8 +662,8 @@ impl VideoData {
     }
 
     /// The frame format type of a video
-    pub fn frame_format_type(&self) -> FrameFormatType {
-        FrameFormatType::try_from(self.p_instance.frame_format_type).unwrap()
+    pub fn frame_format_type(&self) -> Result<FrameFormatType, Error> {
+        FrameFormatType::try_from(self.p_instance.frame_format_type)
     }
 
     /// The timecode of this frame in 100 ns intervals.
@@ -595,8 +684,13 @@ impl VideoData {
     }
 
     /// This is the inter-line stride of the video data, in bytes.
+    ///
+    /// This is `None` for compressed formats, which use [`VideoData::data_size_in_bytes()`] instead.
     pub fn line_stride_in_bytes(&self) -> Option<u32> {
-        // TODO: detect whether it is a compressed type
+        // all FourCC types known to this crate are uncompressed
+        if FourCCVideoType::try_from(self.p_instance.FourCC).is_err() {
+            return None;
+        }
 
         // If the FourCC is not a compressed type, then this will be the
         // inter-line stride of the video data in bytes. If the stride is 0,
@@ -605,12 +699,73 @@ impl VideoData {
     }
 
     /// The size of the p_data buffer in bytes.
+    ///
+    /// This is `None` for uncompressed formats, which use [`VideoData::line_stride_in_bytes()`] instead.
     pub fn data_size_in_bytes(&self) -> Option<u32> {
+        if FourCCVideoType::try_from(self.p_instance.FourCC).is_ok() {
+            return None;
+        }
+
         // If the FourCC is a compressed type, then this will be the size of the
         // p_data buffer in bytes.
         unsafe { Some(self.p_instance.__bindgen_anon_1.data_size_in_bytes as _) }
     }
 
+    /// The memory layout of the frame
+    ///
+    /// Returns `None` for compressed formats.
+    pub fn layout(&self) -> Option<VideoLayout> {
+        let four_cc = FourCCVideoType::try_from(self.p_instance.FourCC).ok()?;
+        Some(VideoLayout::new(
+            four_cc,
+            self.width(),
+            self.height(),
+            self.line_stride_in_bytes()?,
+        ))
+    }
+
+    /// The video data of all planes, laid out as described by [`VideoData::layout()`]
+    ///
+    /// Returns `None` if the frame holds no data.
+    pub fn data(&self) -> Option<&[u8]> {
+        if self.p_instance.p_data.is_null() {
+            return None;
+        }
+
+        let size = match self.layout() {
+            Some(layout) => layout.size_in_bytes(),
+            None => self.data_size_in_bytes()? as usize,
+        };
+        Some(unsafe { std::slice::from_raw_parts(self.p_instance.p_data, size) })
+    }
+
+    /// The data of each plane in memory order, see [`VideoLayout::planes()`]
+    ///
+    /// Returns no planes for compressed formats or if the frame holds no data.
+    pub fn planes(&self) -> Vec<&[u8]> {
+        match (self.layout(), self.data()) {
+            (Some(layout), Some(data)) => layout
+                .planes()
+                .iter()
+                .filter_map(|plane| plane.slice(data))
+                .collect(),
+            _ => Vec::new(),
+        }
+    }
+
+    /// The lines of the given plane, without the padding at the end of each line
+    ///
+    /// Yields nothing if the plane doesn't exist or the frame holds no data.
+    pub fn rows(&self, plane: usize) -> Rows<'_> {
+        let plane = self
+            .layout()
+            .and_then(|layout| layout.planes().get(plane).copied());
+        match (plane, self.data()) {
+            (Some(plane), Some(data)) => plane.rows(data),
+            _ => Rows::empty(),
+        }
+    }
+
     /// A per frame metadata stream that should be XML
     ///
     /// It is sent and received with the frame.
@@ -641,6 +796,14 @@ impl VideoData {
             Some(timestamp)
         }
     }
+
+    /// Copy the frame into an [`OwnedVideoFrame`], which isn't tied to the receiver it was captured from
+    ///
+    /// Returns an error if the FourCC or frame format of the frame is unknown to this crate,
+    /// or if the frame holds no data.
+    pub fn to_owned_frame(&self) -> Result<OwnedVideoFrame, Error> {
+        OwnedVideoFrame::from_video_data(self)
+    }
 }
 
 impl Drop for VideoData {
@@ -649,13 +812,22 @@ impl Drop for VideoData {
             VideoParent::Recv(recv) => unsafe {
                 NDIlib_recv_free_video_v2(***recv, &mut self.p_instance);
             },
-            VideoParent::Owned => {}
+            VideoParent::FrameSync(framesync) => unsafe {
+                NDIlib_framesync_free_video(***framesync, &mut self.p_instance);
+            },
+            VideoParent::Buffer(_) | VideoParent::Owned => {}
         }
     }
 }
 
 enum AudioParent {
     Recv(Arc<OnDrop<NDIlib_recv_instance_t>>),
+    FrameSync(Arc<FrameSyncInstance>),
+    /// The samples are owned by the frame itself
+    ///
+    /// Only held to keep the buffer alive, it is read through `p_instance`.
+    #[allow(dead_code)]
+    Buffer(Vec<f32>),
     Owned,
 }
 
@@ -693,6 +865,50 @@ impl AudioData {
         }
     }
 
+    fn from_binding_framesync(
+        framesync: Arc<FrameSyncInstance>,
+        p_instance: NDIlib_audio_frame_v3_t,
+    ) -> Self {
+        Self {
+            p_instance,
+            parent: AudioParent::FrameSync(framesync),
+        }
+    }
+
+    /// Create a planar frame from a converted v2 frame which points into `buffer`
+    fn from_planar_buffer(frame: &NDIlib_audio_frame_v2_t, buffer: Vec<f32>) -> Self {
+        Self {
+            p_instance: NDIlib_audio_frame_v3_t {
+                sample_rate: frame.sample_rate,
+                no_channels: frame.no_channels,
+                no_samples: frame.no_samples,
+                timecode: frame.timecode,
+                FourCC: FourCCAudioType::FLTP as _,
+                p_data: frame.p_data as _,
+                __bindgen_anon_1: NDIlib_audio_frame_v3_t__bindgen_ty_1 {
+                    channel_stride_in_bytes: frame.channel_stride_in_bytes,
+                },
+                p_metadata: frame.p_metadata,
+                timestamp: frame.timestamp,
+            },
+            parent: AudioParent::Buffer(buffer),
+        }
+    }
+
+    /// The planar floating-point frame in the layout expected by the conversion utilities
+    fn to_binding_v2(&self) -> NDIlib_audio_frame_v2_t {
+        NDIlib_audio_frame_v2_t {
+            sample_rate: self.p_instance.sample_rate,
+            no_channels: self.p_instance.no_channels,
+            no_samples: self.p_instance.no_samples,
+            timecode: self.p_instance.timecode,
+            p_data: self.p_instance.p_data as _,
+            channel_stride_in_bytes: self.channel_stride_in_bytes() as _,
+            p_metadata: self.p_instance.p_metadata,
+            timestamp: self.p_instance.timestamp,
+        }
+    }
+
     /// Create new instance of AudioData
     pub fn new() -> Self {
         Self {
@@ -706,7 +922,7 @@ impl AudioData {
                 __bindgen_anon_1: NDIlib_audio_frame_v3_t__bindgen_ty_1 {
                     channel_stride_in_bytes: 0,
                 },
-                p_metadata: "".as_ptr() as _,
+                p_metadata: null(),
                 timestamp: 0,
             },
             parent: AudioParent::Owned,
@@ -762,12 +978,9 @@ impl AudioData {
     /// What FourCC type is for this frame
     ///
     /// There is currently one supported format: FLTP.
-    pub fn four_cc(&self) -> FourCCAudioType {
-        #[allow(non_upper_case_globals)]
-        match self.p_instance.FourCC {
-            NDIlib_FourCC_audio_type_e_NDIlib_FourCC_type_FLTP => FourCCAudioType::FLTP,
-            x => panic!("Unknown NDI FourCC Audio type encountered: {}", x),
-        }
+    /// Returns an error if the frame uses a format unknown to this crate.
+    pub fn four_cc(&self) -> Result<FourCCAudioType, Error> {
+        FourCCAudioType::try_from(self.p_instance.FourCC)
     }
 
     /// The stride in bytes for a single channel.
@@ -776,11 +989,58 @@ impl AudioData {
     /// channel to another.
     ///
     pub fn channel_stride_in_bytes(&self) -> u32 {
-        match self.four_cc() {
-            FourCCAudioType::FLTP => unsafe {
-                self.p_instance.__bindgen_anon_1.channel_stride_in_bytes as _
-            },
+        unsafe { self.p_instance.__bindgen_anon_1.channel_stride_in_bytes as _ }
+    }
+
+    /// All samples of the frame as a single slice, with `channel_stride_in_bytes` between the channels
+    ///
+    /// The slice ends after the last sample of the last channel, the SDK doesn't pad it up to the stride.
+    fn planar_samples(&self) -> Result<(&[f32], usize), Error> {
+        self.four_cc()?;
+        if self.no_channels() == 0 {
+            return Ok((&[], 0));
         }
+        if self.p_instance.p_data.is_null() {
+            return Err(Error::NoData);
+        }
+
+        let no_samples = self.no_samples() as usize;
+        let stride = self.channel_stride_in_bytes() as usize;
+        let sample_size = std::mem::size_of::<f32>();
+        if stride % sample_size != 0 || stride / sample_size < no_samples {
+            return Err(Error::InvalidLayout);
+        }
+
+        let stride = stride / sample_size;
+        let len = (self.no_channels() as usize - 1) * stride + no_samples;
+        let samples =
+            unsafe { std::slice::from_raw_parts(self.p_instance.p_data as *const f32, len) };
+        Ok((samples, stride))
+    }
+
+    /// The samples of a single channel
+    ///
+    /// Returns an error if the channel doesn't exist or the frame is not in a planar floating-point format.
+    pub fn channel(&self, channel: u32) -> Result<&[f32], Error> {
+        if channel >= self.no_channels() {
+            return Err(Error::OutOfRange("channel", channel as _));
+        }
+
+        let (samples, stride) = self.planar_samples()?;
+        let start = channel as usize * stride;
+        Ok(&samples[start..start + self.no_samples() as usize])
+    }
+
+    /// Iterate over the samples of each channel
+    ///
+    /// Returns an error if the frame is not in a planar floating-point format.
+    pub fn channels(&self) -> Result<impl Iterator<Item = &[f32]>, Error> {
+        let no_samples = self.no_samples() as usize;
+        let (samples, stride) = self.planar_samples()?;
+        Ok((0..self.no_channels() as usize).map(move |channel| {
+            let start = channel * stride;
+            &samples[start..start + no_samples]
+        }))
     }
 
     /// This is a per frame metadata stream in XML
@@ -797,6 +1057,11 @@ impl AudioData {
             .to_string();
         metadata
     }
+
+    /// Copy the samples into an [`OwnedAudioFrame`], which isn't tied to the receiver it was captured from
+    pub fn to_owned_frame(&self) -> OwnedAudioFrame {
+        OwnedAudioFrame::from_audio_data(self)
+    }
 }
 
 impl Drop for AudioData {
@@ -805,7 +1070,10 @@ impl Drop for AudioData {
             AudioParent::Recv(recv) => unsafe {
                 NDIlib_recv_free_audio_v3(***recv, &self.p_instance);
             },
-            AudioParent::Owned => {}
+            AudioParent::FrameSync(framesync) => unsafe {
+                NDIlib_framesync_free_audio_v2(***framesync, &mut self.p_instance);
+            },
+            AudioParent::Buffer(_) | AudioParent::Owned => {}
         }
     }
 }
@@ -857,17 +1125,19 @@ impl MetaData {
     }
 
     /// Create new metadata struct
-    pub fn new(length: u32, timecode: i64, data: String) -> Self {
-        let p_data = CString::new(data).unwrap().into_raw();
+    ///
+    /// Returns an error if `data` contains an interior NUL byte.
+    pub fn new(length: u32, timecode: i64, data: String) -> Result<Self, Error> {
+        let p_data = CString::new(data)?.into_raw();
         let p_instance = NDIlib_metadata_frame_t {
             length: length as _,
             timecode,
             p_data,
         };
-        Self {
+        Ok(Self {
             p_instance,
             parent: MetaDataParent::Owned,
-        }
+        })
     }
 
     /// The length of the string in UTF8 characters. This includes the NULL terminating character.
@@ -905,35 +1175,41 @@ impl Drop for MetaData {
     }
 }
 
-/// Start the library
-///
-/// This is not actually required, but will start the libraries which might get
-/// you slightly better performance in some cases. In general it is more "correct" to
-/// call this although it is not required. There is no way to call this that would have
-/// an adverse impact on anything.
-/// This will return Err if the CPU is not sufficiently capable to run NDILib
-/// currently NDILib requires SSE4.2 instructions (see documentation). You can verify
-/// a specific CPU against the library with a call to [`is_supported_CPU()`]
-pub fn initialize() -> Result<(), NotSupported> {
-    if !unsafe { NDIlib_initialize() } {
-        return Err(NotSupported);
-    };
-
-    Ok(())
+#[test]
+fn source_from_name_and_url() {
+    let source = Source::new("MACHINE (Camera 1)", Some("192.168.0.10:5961")).unwrap();
+    assert_eq!(source.get_name(), "MACHINE (Camera 1)");
+    assert_eq!(source.url_address().as_deref(), Some("192.168.0.10:5961"));
+
+    let cloned = source.clone();
+    drop(source);
+    assert_eq!(cloned.get_name(), "MACHINE (Camera 1)");
+
+    assert!(Source::new("MACHINE (Camera 1)", None)
+        .unwrap()
+        .url_address()
+        .is_none());
+    assert!(Source::new("bad\0name", None).is_err());
 }
 
-/// Destroy everything associated with the library
-///
-/// This is not actually required, but will end the libraries which might get
-/// you slightly better performance in some cases.In general it is more "correct" to
-/// call this although it is not required.
-/// This will destroy everything associated with the library so use it with due caution.
-pub unsafe fn cleanup() {
-    unsafe { NDIlib_destroy() };
-}
-
-/// Recover whether the current CPU in the system is capable of running NDILib.
-#[allow(non_snake_case)]
-pub fn is_supported_CPU() -> bool {
-    unsafe { NDIlib_is_supported_CPU() }
+#[test]
+fn audio_channels_end_at_last_sample() {
+    // the last channel isn't padded up to the stride
+    let mut buffer = vec![0.0, 1.0, 2.0, -1.0, 4.0, 5.0, 6.0];
+    let frame = NDIlib_audio_frame_v2_t {
+        sample_rate: 48000,
+        no_channels: 2,
+        no_samples: 3,
+        timecode: 0,
+        p_data: buffer.as_mut_ptr(),
+        channel_stride_in_bytes: 4 * std::mem::size_of::<f32>() as i32,
+        p_metadata: null(),
+        timestamp: 0,
+    };
+    let audio = AudioData::from_planar_buffer(&frame, buffer);
+    let channels: Vec<_> = audio.channels().unwrap().collect();
+    assert_eq!(channels, vec![&[0.0, 1.0, 2.0][..], &[4.0, 5.0, 6.0][..]]);
+    assert_eq!(audio.channel(1).unwrap(), &[4.0, 5.0, 6.0]);
+
+    assert_eq!(AudioData::new().channels().unwrap().count(), 0);
 }
@@ -0,0 +1,210 @@
+use super::*;
+
+/// White balance modes of a PTZ camera
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum WhiteBalance {
+    /// Automatic white balance
+    Auto,
+    /// Indoor white balance preset
+    Indoor,
+    /// Outdoor white balance preset
+    Outdoor,
+    /// Use the current brightness to automatically set the white balance once
+    OneShot,
+    /// Manual white balance using red and blue values
+    Manual {
+        /// 0.0 (not red) ... 1.0 (very red)
+        red: f32,
+        /// 0.0 (not blue) ... 1.0 (very blue)
+        blue: f32,
+    },
+}
+
+/// Exposure modes of a PTZ camera
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Exposure {
+    /// Automatic exposure
+    Auto,
+    /// Manual exposure parameters
+    Manual {
+        /// 0.0 (dark) ... 1.0 (light)
+        iris: f32,
+        /// 0.0 (dark) ... 1.0 (light)
+        gain: f32,
+        /// 0.0 (slow) ... 1.0 (fast)
+        shutter_speed: f32,
+    },
+}
+
+/// The highest preset number a PTZ camera accepts
+pub const PTZ_MAX_PRESET: u8 = 99;
+
+fn check_range(name: &'static str, value: f32, min: f32, max: f32) -> Result<f32, Error> {
+    // NaN fails both comparisons so it is rejected as well
+    if value >= min && value <= max {
+        Ok(value)
+    } else {
+        Err(Error::OutOfRange(name, value))
+    }
+}
+
+fn check_preset(preset: u8) -> Result<i32, Error> {
+    if preset <= PTZ_MAX_PRESET {
+        Ok(preset as _)
+    } else {
+        Err(Error::OutOfRange("preset", preset as _))
+    }
+}
+
+/// Control handle for a PTZ camera connected to a [`Recv`]
+///
+/// Obtained through [`Recv::ptz()`]. All values are validated against the ranges
+/// documented by the SDK before being sent to the camera.
+pub struct PtzControl {
+    p_instance: Arc<OnDrop<NDIlib_recv_instance_t>>,
+}
+
+unsafe impl core::marker::Send for PtzControl {}
+unsafe impl core::marker::Sync for PtzControl {}
+
+impl PtzControl {
+    pub(crate) fn new(recv: Arc<OnDrop<NDIlib_recv_instance_t>>) -> Self {
+        Self { p_instance: recv }
+    }
+
+    fn result(sent: bool) -> Result<(), Error> {
+        if sent {
+            Ok(())
+        } else {
+            Err(Error::NotConnected)
+        }
+    }
+
+    /// Whether the connected source still reports PTZ support
+    pub fn is_supported(&self) -> bool {
+        unsafe { NDIlib_recv_ptz_is_supported(**self.p_instance) }
+    }
+
+    /// Zoom to an absolute value.
+    ///
+    /// `zoom` = 0.0 (zoomed in) ... 1.0 (zoomed out)
+    pub fn zoom(&self, zoom: f32) -> Result<(), Error> {
+        let zoom = check_range("zoom", zoom, 0.0, 1.0)?;
+        Self::result(unsafe { NDIlib_recv_ptz_zoom(**self.p_instance, zoom) })
+    }
+
+    /// Zoom at a particular speed.
+    ///
+    /// `speed` = -1.0 (zoom outwards) ... +1.0 (zoom inwards)
+    pub fn zoom_speed(&self, speed: f32) -> Result<(), Error> {
+        let speed = check_range("zoom_speed", speed, -1.0, 1.0)?;
+        Self::result(unsafe { NDIlib_recv_ptz_zoom_speed(**self.p_instance, speed) })
+    }
+
+    /// Set the pan and tilt to an absolute value.
+    ///
+    /// `pan` = -1.0 (left) ... 0.0 (centered) ... +1.0 (right)
+    /// `tilt` = -1.0 (bottom) ... 0.0 (centered) ... +1.0 (top)
+    pub fn pan_tilt(&self, pan: f32, tilt: f32) -> Result<(), Error> {
+        let pan = check_range("pan", pan, -1.0, 1.0)?;
+        let tilt = check_range("tilt", tilt, -1.0, 1.0)?;
+        Self::result(unsafe { NDIlib_recv_ptz_pan_tilt(**self.p_instance, pan, tilt) })
+    }
+
+    /// Set the pan and tilt direction and speed.
+    ///
+    /// `pan_speed` = -1.0 (moving right) ... 0.0 (stopped) ... +1.0 (moving left)
+    /// `tilt_speed` = -1.0 (down) ... 0.0 (stopped) ... +1.0 (moving up)
+    pub fn pan_tilt_speed(&self, pan_speed: f32, tilt_speed: f32) -> Result<(), Error> {
+        let pan_speed = check_range("pan_speed", pan_speed, -1.0, 1.0)?;
+        let tilt_speed = check_range("tilt_speed", tilt_speed, -1.0, 1.0)?;
+        Self::result(unsafe {
+            NDIlib_recv_ptz_pan_tilt_speed(**self.p_instance, pan_speed, tilt_speed)
+        })
+    }
+
+    /// Store the current position, focus, etc... as a preset.
+    ///
+    /// `preset` = 0 ... [`PTZ_MAX_PRESET`]
+    pub fn store_preset(&self, preset: u8) -> Result<(), Error> {
+        let preset = check_preset(preset)?;
+        Self::result(unsafe { NDIlib_recv_ptz_store_preset(**self.p_instance, preset) })
+    }
+
+    /// Recall a preset, including position, focus, etc...
+    ///
+    /// `preset` = 0 ... [`PTZ_MAX_PRESET`]
+    /// `speed` = 0.0 (as slow as possible) ... 1.0 (as fast as possible)
+    pub fn recall_preset(&self, preset: u8, speed: f32) -> Result<(), Error> {
+        let preset = check_preset(preset)?;
+        let speed = check_range("speed", speed, 0.0, 1.0)?;
+        Self::result(unsafe { NDIlib_recv_ptz_recall_preset(**self.p_instance, preset, speed) })
+    }
+
+    /// Put the camera in auto-focus.
+    pub fn auto_focus(&self) -> Result<(), Error> {
+        Self::result(unsafe { NDIlib_recv_ptz_auto_focus(**self.p_instance) })
+    }
+
+    /// Focus to an absolute value.
+    ///
+    /// `focus` = 0.0 (focused to infinity) ... 1.0 (focused as close as possible)
+    pub fn focus(&self, focus: f32) -> Result<(), Error> {
+        let focus = check_range("focus", focus, 0.0, 1.0)?;
+        Self::result(unsafe { NDIlib_recv_ptz_focus(**self.p_instance, focus) })
+    }
+
+    /// Focus at a particular speed.
+    ///
+    /// `speed` = -1.0 (focus outwards) ... +1.0 (focus inwards)
+    pub fn focus_speed(&self, speed: f32) -> Result<(), Error> {
+        let speed = check_range("focus_speed", speed, -1.0, 1.0)?;
+        Self::result(unsafe { NDIlib_recv_ptz_focus_speed(**self.p_instance, speed) })
+    }
+
+    /// Set the white balance mode of the camera
+    pub fn white_balance(&self, white_balance: WhiteBalance) -> Result<(), Error> {
+        let instance = **self.p_instance;
+        let sent = match white_balance {
+            WhiteBalance::Auto => unsafe { NDIlib_recv_ptz_white_balance_auto(instance) },
+            WhiteBalance::Indoor => unsafe { NDIlib_recv_ptz_white_balance_indoor(instance) },
+            WhiteBalance::Outdoor => unsafe { NDIlib_recv_ptz_white_balance_outdoor(instance) },
+            WhiteBalance::OneShot => unsafe { NDIlib_recv_ptz_white_balance_oneshot(instance) },
+            WhiteBalance::Manual { red, blue } => {
+                let red = check_range("red", red, 0.0, 1.0)?;
+                let blue = check_range("blue", blue, 0.0, 1.0)?;
+                unsafe { NDIlib_recv_ptz_white_balance_manual(instance, red, blue) }
+            }
+        };
+        Self::result(sent)
+    }
+
+    /// Set the exposure mode of the camera
+    pub fn exposure(&self, exposure: Exposure) -> Result<(), Error> {
+        let instance = **self.p_instance;
+        let sent = match exposure {
+            Exposure::Auto => unsafe { NDIlib_recv_ptz_exposure_auto(instance) },
+            Exposure::Manual {
+                iris,
+                gain,
+                shutter_speed,
+            } => {
+                let iris = check_range("iris", iris, 0.0, 1.0)?;
+                let gain = check_range("gain", gain, 0.0, 1.0)?;
+                let shutter_speed = check_range("shutter_speed", shutter_speed, 0.0, 1.0)?;
+                unsafe { NDIlib_recv_ptz_exposure_manual_v2(instance, iris, gain, shutter_speed) }
+            }
+        };
+        Self::result(sent)
+    }
+}
+
+#[test]
+fn ptz_check_range() {
+    assert_eq!(check_range("zoom", 0.5, 0.0, 1.0).unwrap(), 0.5);
+    assert_eq!(check_range("pan", -1.0, -1.0, 1.0).unwrap(), -1.0);
+    assert!(check_range("zoom", 1.5, 0.0, 1.0).is_err());
+    assert!(check_range("zoom", f32::NAN, 0.0, 1.0).is_err());
+    assert_eq!(check_preset(99).unwrap(), 99);
+    assert!(check_preset(100).is_err());
+}
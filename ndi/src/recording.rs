@@ -0,0 +1,129 @@
+use super::*;
+use std::{mem, os::raw::c_char, time::Duration};
+
+/// The recording times of a remote recorder
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub struct RecordingTimes {
+    /// The number of actual video frames recorded.
+    pub no_frames: i64,
+    /// The starting time of the recording in UTC time, in 100 ns intervals
+    pub start_time: i64,
+    /// The current largest time of the recording in UTC time, in 100 ns intervals
+    pub last_time: i64,
+}
+
+impl RecordingTimes {
+    fn from_binding(times: NDIlib_recv_recording_time_t) -> Self {
+        Self {
+            no_frames: times.no_frames,
+            start_time: times.start_time,
+            last_time: times.last_time,
+        }
+    }
+
+    /// The length of the recording, irrespective of frame rate
+    pub fn duration(&self) -> Duration {
+        let intervals = self.last_time.saturating_sub(self.start_time).max(0) as u64;
+        Duration::from_nanos(intervals.saturating_mul(100))
+    }
+}
+
+/// Control handle for the recorder of a source connected to a [`Recv`]
+///
+/// Obtained through [`Recv::recording()`].
+/// Note that the SDK considers receiver-side recording control deprecated in favour of
+/// the standalone NDI recorder, but it is still supported by many remote systems and NDI|HX devices.
+pub struct Recording {
+    p_instance: Arc<OnDrop<NDIlib_recv_instance_t>>,
+}
+
+unsafe impl core::marker::Send for Recording {}
+unsafe impl core::marker::Sync for Recording {}
+
+impl Recording {
+    pub(crate) fn new(recv: Arc<OnDrop<NDIlib_recv_instance_t>>) -> Self {
+        Self { p_instance: recv }
+    }
+
+    fn result(sent: bool) -> Result<(), Error> {
+        if sent {
+            Ok(())
+        } else {
+            Err(Error::NotConnected)
+        }
+    }
+
+    /// Take ownership of a string allocated by the SDK and free it
+    fn take_string(&self, p_str: *const c_char) -> Option<String> {
+        if p_str.is_null() {
+            return None;
+        }
+        let string = unsafe { CStr::from_ptr(p_str) }
+            .to_string_lossy()
+            .to_string();
+        unsafe { NDIlib_recv_free_string(**self.p_instance, p_str) };
+        Some(string)
+    }
+
+    /// Start recording.
+    ///
+    /// If the recorder was already recording then the message is ignored.
+    /// The filename is only a hint, since the recorder might already be recording or
+    /// might not allow complete flexibility over its filename.
+    /// If no filename is given a name will be chosen automatically.
+    pub fn start(&self, filename_hint: Option<&str>) -> Result<(), Error> {
+        let cstr_filename = match filename_hint {
+            Some(filename) => Some(CString::new(filename)?),
+            None => None,
+        };
+        let p_filename = cstr_filename.as_ref().map_or(null(), |cstr| cstr.as_ptr());
+
+        Self::result(unsafe { NDIlib_recv_recording_start(**self.p_instance, p_filename) })
+    }
+
+    /// Stop recording.
+    pub fn stop(&self) -> Result<(), Error> {
+        Self::result(unsafe { NDIlib_recv_recording_stop(**self.p_instance) })
+    }
+
+    /// Whether the source is currently recording.
+    ///
+    /// Since multiple receivers might be connected to the recorder,
+    /// the recording might have been started by someone else.
+    pub fn is_recording(&self) -> bool {
+        unsafe { NDIlib_recv_recording_is_recording(**self.p_instance) }
+    }
+
+    /// Set the audio level of the recording in decibels relative to the reference level of the source.
+    ///
+    /// Not all recording sources support controlling audio levels.
+    pub fn set_audio_level(&self, level_db: f32) -> Result<(), Error> {
+        Self::result(unsafe { NDIlib_recv_recording_set_audio_level(**self.p_instance, level_db) })
+    }
+
+    /// The current filename of the recording.
+    ///
+    /// This contains a UNC path when one is available, otherwise the local filename on the recorder.
+    /// It remains valid after the recording has stopped until the next recording is started.
+    pub fn filename(&self) -> Option<String> {
+        self.take_string(unsafe { NDIlib_recv_recording_get_filename(**self.p_instance) })
+    }
+
+    /// The error reported by the recorder, if there is one
+    pub fn last_error(&self) -> Option<String> {
+        self.take_string(unsafe { NDIlib_recv_recording_get_error(**self.p_instance) })
+    }
+
+    /// The current recording times
+    pub fn times(&self) -> Result<RecordingTimes, Error> {
+        let mut p_times: mem::MaybeUninit<NDIlib_recv_recording_time_t> =
+            mem::MaybeUninit::uninit();
+        if !unsafe { NDIlib_recv_recording_get_times(**self.p_instance, p_times.as_mut_ptr()) } {
+            return Err(Error::NotRecording);
+        }
+
+        Ok(RecordingTimes::from_binding(unsafe {
+            p_times.assume_init()
+        }))
+    }
+}
@@ -1,5 +1,10 @@
 use super::*;
-use std::{ffi::CString, fmt::Display, mem, sync::Mutex};
+use std::{
+    ffi::CString,
+    fmt::Display,
+    mem,
+    sync::{mpsc, Mutex},
+};
 
 /// Current performance levels of the receiving.
 ///
@@ -146,17 +151,19 @@ impl RecvBuilder {
     }
 
     /// Build the [`Recv`]
-    pub fn build(self) -> Result<Recv, RecvCreateError> {
+    pub fn build(self) -> Result<Recv, Error> {
         // From default C++ constructor in Processing.NDI.Recv.h
         let mut settings: NDIlib_recv_create_v3_t = NDIlib_recv_create_v3_t {
-            source_to_connect_to: Source::new().p_instance,
+            source_to_connect_to: Source::empty().p_instance,
             color_format: RecvColorFormat::UYVY_BGRA as _,
             bandwidth: RecvBandwidth::Highest as _,
             allow_video_fields: true,
             p_ndi_recv_name: null(),
         };
 
-        if let Some(src) = self.source_to_connect_to {
+        // the source has to outlive the call to create, since it may own its strings
+        let source_to_connect_to = self.source_to_connect_to;
+        if let Some(src) = &source_to_connect_to {
             settings.source_to_connect_to = src.p_instance;
         }
         if let Some(color_format) = self.color_format {
@@ -170,7 +177,7 @@ impl RecvBuilder {
         }
         if let Some(ndi_recv_name) = self.ndi_recv_name {
             // String shouldn't contain a 0-byte
-            let cstr = CString::new(ndi_recv_name).unwrap();
+            let cstr = CString::new(ndi_recv_name)?;
 
             settings.p_ndi_recv_name = cstr.into_raw();
         }
@@ -209,54 +216,160 @@ impl RecvQueueSize {
     }
 }
 
+/// A frame captured by [`Recv::capture()`]
+#[derive(Debug)]
+pub enum Frame {
+    /// A video frame
+    Video(VideoData),
+    /// An audio frame
+    Audio(AudioData),
+    /// A metadata frame
+    Metadata(MetaData),
+    /// The settings of the source have changed
+    ///
+    /// For instance the web URL has changed or the device is now known to be a PTZ camera.
+    StatusChange,
+    /// No data was received within the timeout
+    None,
+}
+
+/// The state of the connection of a [`Recv`] to its source
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ConnectionState {
+    /// The receiver isn't connected to any source
+    Disconnected,
+    /// The receiver was told to connect to a source, which hasn't been reached yet
+    Connecting,
+    /// The receiver is connected to its source
+    Connected,
+    /// The connection to the source was lost, the SDK keeps trying to reconnect
+    Lost,
+}
+
+impl ConnectionState {
+    /// The state after observing the number of connections of the receiver
+    fn with_connections(self, connections: u32) -> Self {
+        match self {
+            ConnectionState::Disconnected => ConnectionState::Disconnected,
+            _ if connections > 0 => ConnectionState::Connected,
+            ConnectionState::Connected => ConnectionState::Lost,
+            state => state,
+        }
+    }
+
+    /// The state after observing the result of a capture
+    ///
+    /// `connections` is the number of connections, which is only needed if the capture didn't yield a frame.
+    fn with_capture(self, result: &Result<Frame, Error>, connections: Option<u32>) -> Self {
+        match result {
+            _ if self == ConnectionState::Disconnected => self,
+            Ok(Frame::Video(_)) | Ok(Frame::Audio(_)) | Ok(Frame::Metadata(_)) => {
+                ConnectionState::Connected
+            }
+            Ok(Frame::StatusChange) | Ok(Frame::None) => {
+                connections.map_or(self, |connections| self.with_connections(connections))
+            }
+            // a receiver which never connected hasn't lost anything
+            Err(Error::NotConnected) if self == ConnectionState::Connected => ConnectionState::Lost,
+            Err(_) => self,
+        }
+    }
+}
+
+/// Tracks the [`ConnectionState`] of a [`Recv`] and notifies subscribers about changes
+struct Connection {
+    state: Mutex<ConnectionState>,
+    subscribers: Mutex<Vec<mpsc::Sender<ConnectionState>>>,
+}
+
+impl Connection {
+    fn new(state: ConnectionState) -> Self {
+        Self {
+            state: Mutex::new(state),
+            subscribers: Mutex::new(vec![]),
+        }
+    }
+
+    fn get(&self) -> ConnectionState {
+        *self.state.lock().unwrap_or_else(PoisonError::into_inner)
+    }
+
+    fn update(&self, next: impl FnOnce(ConnectionState) -> ConnectionState) {
+        let mut state = self.state.lock().unwrap_or_else(PoisonError::into_inner);
+        let new_state = next(*state);
+        if new_state != *state {
+            *state = new_state;
+            // the state stays locked, so subscribers see the changes in order
+            self.subscribers
+                .lock()
+                .unwrap_or_else(PoisonError::into_inner)
+                .retain(|subscriber| subscriber.send(new_state).is_ok());
+        }
+    }
+
+    fn subscribe(&self) -> mpsc::Receiver<ConnectionState> {
+        let (tx, rx) = mpsc::channel();
+        self.subscribers
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner)
+            .push(tx);
+        rx
+    }
+}
+
 /// The NDI receiver struct
 pub struct Recv {
-    /// whether the Recv is currently connected
-    pub connected: bool,
-    p_instance: Arc<OnDrop<NDIlib_recv_instance_t>>,
-    guard: Mutex<()>,
+    pub(crate) p_instance: Arc<OnDrop<NDIlib_recv_instance_t>>,
+    guard: Arc<Mutex<()>>,
+    connection: Arc<Connection>,
 }
 
 unsafe impl core::marker::Send for Recv {}
 unsafe impl core::marker::Sync for Recv {}
 
 impl Recv {
-    fn with_settings(settings: NDIlib_recv_create_v3_t) -> Result<Self, RecvCreateError> {
+    fn with_settings(settings: NDIlib_recv_create_v3_t) -> Result<Self, Error> {
+        let runtime = NdiRuntime::initialize()?;
         let p_instance = unsafe { NDIlib_recv_create_v3(&settings) };
         if p_instance.is_null() {
-            return Err(RecvCreateError);
+            return Err(Error::CreateFailed("Recv"));
         }
 
-        let guard = Mutex::new(());
-        let mut this = Self {
-            p_instance: Arc::new(OnDrop::new(p_instance, |s| unsafe {
-                NDIlib_recv_destroy(s)
-            })),
-            connected: false,
-            guard,
+        let state = if settings.source_to_connect_to.p_ndi_name.is_null() {
+            ConnectionState::Disconnected
+        } else {
+            ConnectionState::Connecting
         };
-
-        this.connected = this.get_no_connections() > 0;
-        Ok(this)
+        let guard = Arc::new(Mutex::new(()));
+        Ok(Self {
+            p_instance: Arc::new(
+                OnDrop::new(p_instance, |s| unsafe { NDIlib_recv_destroy(s) })
+                    .with_runtime(runtime),
+            ),
+            guard,
+            connection: Arc::new(Connection::new(state)),
+        })
     }
 
     /// Create new receiver which isn't connected to any sources
     ///
     /// It is recommended that you use [`RecvBuilder`] instead if possible
-    pub fn new() -> Result<Self, RecvCreateError> {
+    pub fn new() -> Result<Self, Error> {
+        let runtime = NdiRuntime::initialize()?;
         let p_instance = unsafe { NDIlib_recv_create_v3(null()) };
 
         if p_instance.is_null() {
-            return Err(RecvCreateError);
+            return Err(Error::CreateFailed("Recv"));
         }
 
-        let guard = Mutex::new(());
+        let guard = Arc::new(Mutex::new(()));
         Ok(Self {
-            p_instance: Arc::new(OnDrop::new(p_instance, |s| unsafe {
-                NDIlib_recv_destroy(s)
-            })),
-            connected: false,
+            p_instance: Arc::new(
+                OnDrop::new(p_instance, |s| unsafe { NDIlib_recv_destroy(s) })
+                    .with_runtime(runtime),
+            ),
             guard,
+            connection: Arc::new(Connection::new(ConnectionState::Disconnected)),
         })
     }
 
@@ -264,6 +377,7 @@ impl Recv {
     pub fn connect(&mut self, source: &Source) {
         let instance: *const NDIlib_source_t = &source.p_instance;
         unsafe { NDIlib_recv_connect(**self.p_instance, instance) };
+        self.connection.update(|_| ConnectionState::Connecting);
     }
 
     /// Disconnect from all sources
@@ -271,157 +385,193 @@ impl Recv {
         unsafe {
             NDIlib_recv_connect(**self.p_instance, null());
         }
+        self.connection.update(|_| ConnectionState::Disconnected);
     }
 
-    /// Receive video, audio and metadata frames.
+    /// The current state of the connection to the source
+    ///
+    /// The state is updated by every capture, as well as by this call.
+    pub fn state(&self) -> ConnectionState {
+        // query the SDK before locking the state
+        let connections = self.get_no_connections();
+        self.connection
+            .update(|state| state.with_connections(connections));
+        self.connection.get()
+    }
+
+    /// Subscribe to changes of the [`ConnectionState`]
+    ///
+    /// Every change is sent to the returned channel, which can be received from on any thread.
+    /// Changes are only detected while capturing or calling [`Recv::state()`].
+    pub fn connection_events(&self) -> mpsc::Receiver<ConnectionState> {
+        self.connection.subscribe()
+    }
+
+    /// Another handle to the same receiver, which shares its lock
+    pub(crate) fn share(&self) -> Self {
+        Self {
+            p_instance: Arc::clone(&self.p_instance),
+            guard: Arc::clone(&self.guard),
+            connection: Arc::clone(&self.connection),
+        }
+    }
+
+    /// Receive the next video, audio or metadata frame
     ///
     /// This call can be called simultaneously on separate threads,
     /// so it is entirely possible to receive audio, video, metadata all on separate threads.
+    /// Returns [`Frame::None`] if no data is received within the specified timeout
+    /// and [`Error::NotConnected`] if the connection is lost.
+    pub fn capture(&self, timeout: Duration) -> Result<Frame, Error> {
+        self.capture_frame(true, true, true, timeout)
+    }
+
+    /// Receive the next video frame
+    ///
+    /// Returns `None` if no video frame is received within the specified timeout
+    /// or the status of the source changed in the meantime.
+    pub fn capture_video(&self, timeout: Duration) -> Result<Option<VideoData>, Error> {
+        match self.capture_frame(true, false, false, timeout)? {
+            Frame::Video(video) => Ok(Some(video)),
+            _ => Ok(None),
+        }
+    }
+
+    /// Receive the next audio frame
+    ///
+    /// Returns `None` if no audio frame is received within the specified timeout
+    /// or the status of the source changed in the meantime.
+    pub fn capture_audio(&self, timeout: Duration) -> Result<Option<AudioData>, Error> {
+        match self.capture_frame(false, true, false, timeout)? {
+            Frame::Audio(audio) => Ok(Some(audio)),
+            _ => Ok(None),
+        }
+    }
+
+    /// Receive the next metadata frame
+    ///
+    /// Returns `None` if no metadata frame is received within the specified timeout
+    /// or the status of the source changed in the meantime.
+    pub fn capture_metadata(&self, timeout: Duration) -> Result<Option<MetaData>, Error> {
+        match self.capture_frame(false, false, true, timeout)? {
+            Frame::Metadata(metadata) => Ok(Some(metadata)),
+            _ => Ok(None),
+        }
+    }
+
+    /// Receive video, audio and metadata frames.
+    ///
+    /// The received frame is stored in the matching `Option`, the others are reset to `None`.
     /// This function will return [`FrameType::None`] if no data is received within the specified timeout
     /// and [`FrameType::ErrorFrame`] if the connection is lost.
-    /// Buffers captured with this must be freed with the appropriate free function.
+    #[deprecated(note = "use `Recv::capture()` instead")]
     pub fn capture_all(
         &self,
         video_data: &mut Option<VideoData>,
         audio_data: &mut Option<AudioData>,
         meta_data: &mut Option<MetaData>,
         timeout_ms: u32,
-    ) -> FrameType {
-        let mut video = if let Some(x) = video_data {
-            mem::MaybeUninit::new(x.p_instance)
-        } else {
-            mem::MaybeUninit::zeroed()
-        };
+    ) -> Result<FrameType, Error> {
+        *video_data = None;
+        *audio_data = None;
+        *meta_data = None;
+
+        let timeout = Duration::from_millis(timeout_ms as _);
+        match self.capture(timeout) {
+            Ok(Frame::Video(video)) => {
+                *video_data = Some(video);
+                Ok(FrameType::Video)
+            }
+            Ok(Frame::Audio(audio)) => {
+                *audio_data = Some(audio);
+                Ok(FrameType::Audio)
+            }
+            Ok(Frame::Metadata(metadata)) => {
+                *meta_data = Some(metadata);
+                Ok(FrameType::Metadata)
+            }
+            Ok(Frame::StatusChange) => Ok(FrameType::StatusChange),
+            Ok(Frame::None) => Ok(FrameType::None),
+            Err(Error::NotConnected) => Ok(FrameType::ErrorFrame),
+            Err(e) => Err(e),
+        }
+    }
 
-        let mut audio = if let Some(x) = audio_data {
-            mem::MaybeUninit::new(x.p_instance)
-        } else {
-            mem::MaybeUninit::zeroed()
+    /// Capture a frame of one of the requested types and update the connection state
+    fn capture_frame(
+        &self,
+        video: bool,
+        audio: bool,
+        metadata: bool,
+        timeout: Duration,
+    ) -> Result<Frame, Error> {
+        let result = self.capture_raw(video, audio, metadata, timeout);
+        // query the SDK before locking the state
+        let connections = match result {
+            Ok(Frame::StatusChange) | Ok(Frame::None) => Some(self.get_no_connections()),
+            _ => None,
         };
+        self.connection
+            .update(|state| state.with_capture(&result, connections));
+        result
+    }
 
-        let mut metadata = if let Some(x) = meta_data {
-            mem::MaybeUninit::new(x.p_instance)
-        } else {
-            mem::MaybeUninit::zeroed()
-        };
+    fn capture_raw(
+        &self,
+        video: bool,
+        audio: bool,
+        metadata: bool,
+        timeout: Duration,
+    ) -> Result<Frame, Error> {
+        let mut p_video = mem::MaybeUninit::<NDIlib_video_frame_v2_t>::zeroed();
+        let mut p_audio = mem::MaybeUninit::<NDIlib_audio_frame_v3_t>::zeroed();
+        let mut p_metadata = mem::MaybeUninit::<NDIlib_metadata_frame_t>::zeroed();
 
         let response = unsafe {
             NDIlib_recv_capture_v3(
                 **self.p_instance,
-                video.as_mut_ptr(),
-                audio.as_mut_ptr(),
-                metadata.as_mut_ptr(),
-                timeout_ms,
+                if video {
+                    p_video.as_mut_ptr()
+                } else {
+                    null_mut()
+                },
+                if audio {
+                    p_audio.as_mut_ptr()
+                } else {
+                    null_mut()
+                },
+                if metadata {
+                    p_metadata.as_mut_ptr()
+                } else {
+                    null_mut()
+                },
+                timeout_ms(timeout),
             )
         };
 
-        if !video.as_ptr().is_null() {
-            *video_data = Some(VideoData::from_binding_recv(
-                self.p_instance.clone(),
-                unsafe { video.assume_init() },
-            ));
-        }
-
-        if !audio.as_ptr().is_null() {
-            *audio_data = Some(AudioData::from_binding_recv(
-                self.p_instance.clone(),
-                unsafe { audio.assume_init() },
-            ));
-        }
-
-        if !metadata.as_ptr().is_null() {
-            *meta_data = Some(MetaData::from_binding_recv(
-                self.p_instance.clone(),
-                unsafe { metadata.assume_init() },
-            ));
-        }
-
-        FrameType::try_from(response).unwrap()
-    }
-
-    /// Receive video frame
-    pub fn capture_video(&self, video_data: &mut Option<VideoData>, timeout_ms: u32) -> FrameType {
-        unsafe {
-            let mut video = if let Some(x) = video_data {
-                mem::MaybeUninit::new(x.p_instance)
-            } else {
-                mem::MaybeUninit::zeroed()
-            };
-
-            let response = NDIlib_recv_capture_v3(
-                **self.p_instance,
-                video.as_mut_ptr(),
-                null_mut(),
-                null_mut(),
-                timeout_ms,
-            );
-
-            if !video.as_ptr().is_null() {
-                *video_data = Some(VideoData::from_binding_recv(
-                    self.p_instance.clone(),
-                    video.assume_init(),
-                ));
-            }
-
-            FrameType::try_from(response).unwrap()
-        }
-    }
-
-    /// Receive audio frame
-    pub fn capture_audio(&self, audio_data: &mut Option<AudioData>, timeout_ms: u32) -> FrameType {
-        unsafe {
-            let mut audio = if let Some(x) = audio_data {
-                mem::MaybeUninit::new(x.p_instance)
-            } else {
-                mem::MaybeUninit::zeroed()
-            };
-            let response = NDIlib_recv_capture_v3(
-                **self.p_instance,
-                null_mut(),
-                audio.as_mut_ptr(),
-                null_mut(),
-                timeout_ms,
-            );
-
-            if !audio.as_ptr().is_null() {
-                *audio_data = Some(AudioData::from_binding_recv(
-                    self.p_instance.clone(),
-                    audio.assume_init(),
-                ));
-            }
-            FrameType::try_from(response).unwrap()
-        }
-    }
-
-    /// Receive metadata frame
-    pub fn capture_metadata(&self, meta_data: &mut Option<MetaData>, timeout_ms: u32) -> FrameType {
-        unsafe {
-            let mut metadata = if let Some(x) = meta_data {
-                mem::MaybeUninit::new(x.p_instance)
-            } else {
-                mem::MaybeUninit::zeroed()
-            };
-            let response = NDIlib_recv_capture_v3(
-                **self.p_instance,
-                null_mut(),
-                null_mut(),
-                metadata.as_mut_ptr(),
-                timeout_ms,
-            );
-
-            if !metadata.as_ptr().is_null() {
-                *meta_data = Some(MetaData::from_binding_recv(
-                    Arc::clone(&self.p_instance),
-                    metadata.assume_init(),
-                ));
-            }
-            FrameType::try_from(response).unwrap()
+        // the SDK only fills in the frame matching the returned type
+        match FrameType::try_from(response)? {
+            FrameType::Video => Ok(Frame::Video(VideoData::from_binding_recv(
+                Arc::clone(&self.p_instance),
+                unsafe { p_video.assume_init() },
+            ))),
+            FrameType::Audio => Ok(Frame::Audio(AudioData::from_binding_recv(
+                Arc::clone(&self.p_instance),
+                unsafe { p_audio.assume_init() },
+            ))),
+            FrameType::Metadata => Ok(Frame::Metadata(MetaData::from_binding_recv(
+                Arc::clone(&self.p_instance),
+                unsafe { p_metadata.assume_init() },
+            ))),
+            FrameType::StatusChange => Ok(Frame::StatusChange),
+            FrameType::None => Ok(Frame::None),
+            FrameType::ErrorFrame => Err(Error::NotConnected),
         }
     }
 
     /// Get the performance metrics (total, dropped)
     pub fn get_performance(&self) -> (RecvPerformance, RecvPerformance) {
-        let _lock = self.guard.lock().unwrap();
+        let _lock = self.guard.lock().unwrap_or_else(PoisonError::into_inner);
         let mut p_total: mem::MaybeUninit<NDIlib_recv_performance_t> = mem::MaybeUninit::uninit();
         let mut p_dropped: mem::MaybeUninit<NDIlib_recv_performance_t> = mem::MaybeUninit::uninit();
         unsafe {
@@ -440,7 +590,7 @@ impl Recv {
 
     /// Get the current queue sizes
     pub fn get_queue(&self) -> RecvQueueSize {
-        let _lock = self.guard.lock().unwrap();
+        let _lock = self.guard.lock().unwrap_or_else(PoisonError::into_inner);
         let mut p_total: mem::MaybeUninit<NDIlib_recv_queue_t> = mem::MaybeUninit::uninit();
         unsafe {
             NDIlib_recv_get_queue(**self.p_instance, p_total.as_mut_ptr());
@@ -452,25 +602,63 @@ impl Recv {
 
     /// Get the current number of sources connected to
     pub fn get_no_connections(&self) -> u32 {
-        let _lock = self.guard.lock().unwrap();
+        let _lock = self.guard.lock().unwrap_or_else(PoisonError::into_inner);
         unsafe { NDIlib_recv_get_no_connections(**self.p_instance) as _ }
     }
 
     /// Set tally info for sender
     pub fn set_tally(&mut self, tally: Tally) {
-        let _lock = self.guard.lock().unwrap();
+        let _lock = self.guard.lock().unwrap_or_else(PoisonError::into_inner);
         unsafe {
             NDIlib_recv_set_tally(**self.p_instance, &tally.into());
         }
     }
 
+    /// Whether the connected source supports PTZ control
+    ///
+    /// Note that it might take a second or two after the connection for this value to be set.
+    /// The value might have changed whenever a capture returns [`FrameType::StatusChange`].
+    pub fn ptz_is_supported(&self) -> bool {
+        unsafe { NDIlib_recv_ptz_is_supported(**self.p_instance) }
+    }
+
+    /// Get a [`PtzControl`] handle for the connected PTZ camera
+    ///
+    /// Returns [`Error::NotConnected`] if no source is connected
+    /// and [`Error::NotSupported`] if the source isn't a PTZ camera.
+    pub fn ptz(&self) -> Result<PtzControl, Error> {
+        if self.get_no_connections() == 0 {
+            return Err(Error::NotConnected);
+        }
+        if !self.ptz_is_supported() {
+            return Err(Error::NotSupported);
+        }
+
+        Ok(PtzControl::new(Arc::clone(&self.p_instance)))
+    }
+
+    /// Get a [`Recording`] handle for the recorder of the connected source
+    ///
+    /// Returns [`Error::NotConnected`] if no source is connected
+    /// and [`Error::NotSupported`] if the source doesn't support recording control.
+    pub fn recording(&self) -> Result<Recording, Error> {
+        if self.get_no_connections() == 0 {
+            return Err(Error::NotConnected);
+        }
+        if !unsafe { NDIlib_recv_recording_is_supported(**self.p_instance) } {
+            return Err(Error::NotSupported);
+        }
+
+        Ok(Recording::new(Arc::clone(&self.p_instance)))
+    }
+
     /// Add a connection metadata string to the list of what is sent on each new connection.
     ///
     /// If someone is already connected then this string will be sent to them immediately.
     /// Connection based metadata is data that is sent automatically each time a new connection is received.
     /// To reset them you need to clear them all and set them up again using [`Recv::recv_clear_connection_metadata()`]
     pub fn add_connection_metadata(&self, metadata: &MetaData) {
-        let _lock = self.guard.lock().unwrap();
+        let _lock = self.guard.lock().unwrap_or_else(PoisonError::into_inner);
         unsafe {
             NDIlib_recv_add_connection_metadata(**self.p_instance, &metadata.p_instance);
         }
@@ -480,15 +668,51 @@ impl Recv {
     ///
     /// This returns `false` if we are not currently connected to anything.
     pub fn send_metadata(&self, metadata: &MetaData) -> bool {
-        let _lock = self.guard.lock().unwrap();
+        let _lock = self.guard.lock().unwrap_or_else(PoisonError::into_inner);
         unsafe { NDIlib_recv_send_metadata(**self.p_instance, &metadata.p_instance) }
     }
 
     /// Clear all connection metadata
     pub fn recv_clear_connection_metadata(&self) {
-        let _lock = self.guard.lock().unwrap();
+        let _lock = self.guard.lock().unwrap_or_else(PoisonError::into_inner);
         unsafe {
             NDIlib_recv_clear_connection_metadata(**self.p_instance);
         }
     }
 }
+
+#[test]
+fn connection_state_transitions() {
+    use ConnectionState::*;
+
+    assert_eq!(Connecting.with_connections(0), Connecting);
+    assert_eq!(Connecting.with_connections(1), Connected);
+    assert_eq!(Connected.with_connections(0), Lost);
+    assert_eq!(Lost.with_connections(1), Connected);
+    assert_eq!(Disconnected.with_connections(1), Disconnected);
+
+    let lost = Err(Error::NotConnected);
+    assert_eq!(Connected.with_capture(&lost, None), Lost);
+    assert_eq!(Connecting.with_capture(&lost, None), Connecting);
+    assert_eq!(Disconnected.with_capture(&lost, None), Disconnected);
+    assert_eq!(
+        Lost.with_capture(&Ok(Frame::StatusChange), Some(1)),
+        Connected
+    );
+    assert_eq!(Connected.with_capture(&Ok(Frame::None), Some(1)), Connected);
+}
+
+#[test]
+fn connection_events_are_sent_on_change() {
+    let connection = Connection::new(ConnectionState::Disconnected);
+    let events = connection.subscribe();
+    connection.update(|_| ConnectionState::Connecting);
+    connection.update(|_| ConnectionState::Connecting);
+    connection.update(|state| state.with_connections(1));
+
+    let events: Vec<_> = events.try_iter().collect();
+    assert_eq!(
+        events,
+        vec![ConnectionState::Connecting, ConnectionState::Connected]
+    );
+}
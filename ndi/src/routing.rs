@@ -0,0 +1,125 @@
+use super::*;
+use std::ffi::CString;
+
+/// Builder struct for [`Router`]
+#[derive(Debug, Clone)]
+pub struct RouterBuilder {
+    ndi_name: Option<String>,
+    groups: Option<String>,
+}
+
+impl RouterBuilder {
+    /// Create new builder instance
+    pub fn new() -> Self {
+        Self {
+            ndi_name: None,
+            groups: None,
+        }
+    }
+
+    /// This is the name of the NDI source to create.
+    ///
+    /// This will be the name of the routed NDI source on the network.
+    /// For instance, if your network machine name is called “MyMachine” and you
+    /// specify this parameter as “Output 1”, the NDI source on the network would be “MyMachine (Output 1)”.
+    pub fn ndi_name(mut self, ndi_name: String) -> Self {
+        self.ndi_name = Some(ndi_name);
+        self
+    }
+
+    /// Specify the groups that this NDI router should place itself into.
+    ///
+    /// Groups are sets of NDI sources. Any source can be part of any
+    /// number of groups, and groups are comma-separated. For instance
+    /// "cameras,studio 1,10am show" would place a source in the three groups named.
+    pub fn groups(mut self, groups: String) -> Self {
+        self.groups = Some(groups);
+        self
+    }
+
+    /// Build the [`Router`] instance
+    pub fn build(self) -> Result<Router, Error> {
+        let mut settings = NDIlib_routing_create_t {
+            p_ndi_name: null(),
+            p_groups: null(),
+        };
+
+        let cstr_ndi_name: CString;
+        let cstr_ndi_group: CString;
+
+        if let Some(ndi_name) = self.ndi_name {
+            cstr_ndi_name = CString::new(ndi_name)?;
+            settings.p_ndi_name = cstr_ndi_name.as_ptr();
+        }
+
+        if let Some(groups) = self.groups {
+            cstr_ndi_group = CString::new(groups)?;
+            settings.p_groups = cstr_ndi_group.as_ptr();
+        }
+
+        Router::with_settings(settings)
+    }
+}
+
+/// A virtual NDI source which routes another source to its receivers
+///
+/// Receivers connected to the router are switched over to the routed source without
+/// any video passing through this process, which allows building patch-bay style outputs.
+pub struct Router {
+    p_instance: Arc<OnDrop<NDIlib_routing_instance_t>>,
+}
+
+unsafe impl core::marker::Send for Router {}
+unsafe impl core::marker::Sync for Router {}
+
+impl Router {
+    fn with_settings(settings: NDIlib_routing_create_t) -> Result<Self, Error> {
+        let runtime = NdiRuntime::initialize()?;
+        let p_instance = unsafe { NDIlib_routing_create(&settings) };
+
+        if p_instance.is_null() {
+            return Err(Error::CreateFailed("Router"));
+        }
+
+        Ok(Self {
+            p_instance: Arc::new(
+                OnDrop::new(p_instance, |s| unsafe { NDIlib_routing_destroy(s) })
+                    .with_runtime(runtime),
+            ),
+        })
+    }
+
+    /// Route this output to another source
+    ///
+    /// the return value is whether the routing was changed or not
+    pub fn route_to(&self, source: &Source) -> bool {
+        unsafe { NDIlib_routing_change(**self.p_instance, &source.p_instance) }
+    }
+
+    /// Clear the routing, so receivers of this output receive nothing
+    ///
+    /// the return value is whether the routing was changed or not
+    pub fn clear(&self) -> bool {
+        unsafe { NDIlib_routing_clear(**self.p_instance) }
+    }
+
+    /// Get the current number of receivers connected to this output.
+    ///
+    /// If you specify a timeout that is not 0 then it will wait until there are connections for this amount of time.
+    pub fn get_no_connections(&self, timeout_ms: u32) -> u32 {
+        unsafe { NDIlib_routing_get_no_connections(**self.p_instance, timeout_ms) as _ }
+    }
+
+    /// Retrieve the source information for this router.
+    ///
+    /// Returns `None` if the SDK doesn't provide the source information.
+    pub fn get_source(&self) -> Option<Source> {
+        let instance = unsafe { NDIlib_routing_get_source_name(**self.p_instance) };
+        if instance.is_null() {
+            return None;
+        }
+
+        let parent = SourceParent::Routing(Arc::clone(&self.p_instance));
+        Some(Source::from_binding(parent, unsafe { *instance }))
+    }
+}
@@ -0,0 +1,77 @@
+use super::*;
+use std::sync::Mutex;
+
+/// The number of live [`NdiRuntime`] handles
+///
+/// All changes happen under the lock, so initializing and destroying the library never overlap.
+static REFERENCES: Mutex<usize> = Mutex::new(0);
+
+fn references() -> std::sync::MutexGuard<'static, usize> {
+    REFERENCES.lock().unwrap_or_else(|e| e.into_inner())
+}
+
+/// A reference counted handle to the NDI library
+///
+/// The library is initialized when the first handle is created and destroyed once the last handle is dropped.
+/// Every [`Find`], [`Recv`], [`Send`] and [`Router`] holds a handle for as long as it or any frame
+/// captured from it is alive, so the library can't be destroyed while it is still in use.
+/// Keeping a handle around yourself avoids re-initializing the library when these are created and dropped repeatedly.
+#[derive(Debug)]
+pub struct NdiRuntime {
+    _private: (),
+}
+
+impl NdiRuntime {
+    /// Initialize the library, or acquire another handle if it is already initialized
+    ///
+    /// This will return Err if the CPU is not sufficiently capable to run NDILib,
+    /// currently NDILib requires SSE4.2 instructions (see documentation).
+    ///
+    /// With the `dynamic-link` feature this also loads the NDI runtime, which is looked up in
+    /// the directory given by the `NDI_RUNTIME_DIR_V5` environment variable and then in the
+    /// search path of the system.
+    pub fn initialize() -> Result<Self, Error> {
+        let mut references = references();
+        if *references == 0 {
+            #[cfg(feature = "dynamic-link")]
+            internal::dynamic::load().map_err(Error::LoadFailed)?;
+
+            if !unsafe { NDIlib_initialize() } {
+                return Err(Error::NotSupported);
+            }
+        }
+
+        *references += 1;
+        Ok(Self { _private: () })
+    }
+
+    /// The version string of the NDI library
+    pub fn version(&self) -> String {
+        unsafe { CStr::from_ptr(NDIlib_version()) }
+            .to_string_lossy()
+            .to_string()
+    }
+
+    /// Recover whether the current CPU in the system is capable of running NDILib.
+    #[allow(non_snake_case)]
+    pub fn is_supported_CPU(&self) -> bool {
+        unsafe { NDIlib_is_supported_CPU() }
+    }
+}
+
+impl Clone for NdiRuntime {
+    fn clone(&self) -> Self {
+        *references() += 1;
+        Self { _private: () }
+    }
+}
+
+impl Drop for NdiRuntime {
+    fn drop(&mut self) {
+        let mut references = references();
+        *references -= 1;
+        if *references == 0 {
+            unsafe { NDIlib_destroy() };
+        }
+    }
+}
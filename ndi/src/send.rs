@@ -1,5 +1,15 @@
 use super::*;
-use std::{convert::TryFrom, ffi::CString, mem::MaybeUninit};
+use std::{
+    convert::TryFrom,
+    ffi::CString,
+    mem::MaybeUninit,
+    sync::{
+        atomic::{AtomicBool, AtomicU64, Ordering},
+        mpsc, Condvar, Mutex,
+    },
+    thread::{self, JoinHandle},
+    time::Instant,
+};
 
 /// Builder struct for [`Send`]
 #[derive(Debug, Clone)]
@@ -8,6 +18,7 @@ pub struct SendBuilder {
     groups: Option<String>,
     clock_video: Option<bool>,
     clock_audio: Option<bool>,
+    failover: Option<Source>,
 }
 
 impl SendBuilder {
@@ -18,6 +29,7 @@ impl SendBuilder {
             groups: None,
             clock_video: None,
             clock_audio: None,
+            failover: None,
         }
     }
 
@@ -69,25 +81,33 @@ impl SendBuilder {
         self
     }
 
+    /// Specify a failover [`Source`] for this sender.
+    ///
+    /// See [`Send::set_failover()`]
+    pub fn failover(mut self, failover: Source) -> Self {
+        self.failover = Some(failover);
+        self
+    }
+
     /// Build the [`Send`] instance
-    pub fn build(self) -> Result<Send, SendCreateError> {
+    pub fn build(self) -> Result<Send, Error> {
         let mut settings = NDIlib_send_create_t {
             p_ndi_name: null(),
             p_groups: null(),
             clock_video: true,
             clock_audio: true,
         };
-        
+
         let cstr_ndi_name: CString;
         let cstr_ndi_group: CString;
 
         if let Some(ndi_name) = self.ndi_name {
-            cstr_ndi_name = CString::new(ndi_name).unwrap();
+            cstr_ndi_name = CString::new(ndi_name)?;
             settings.p_ndi_name = cstr_ndi_name.as_ptr();
         }
 
         if let Some(groups) = self.groups {
-            cstr_ndi_group = CString::new(groups).unwrap();
+            cstr_ndi_group = CString::new(groups)?;
             settings.p_groups = cstr_ndi_group.as_ptr();
         }
 
@@ -99,106 +119,150 @@ impl SendBuilder {
             settings.clock_audio = clock_audio;
         }
 
-        Send::with_settings(settings)
+        let send = Send::with_settings(settings)?;
+        if let Some(failover) = self.failover {
+            send.set_failover(Some(&failover));
+        }
+
+        Ok(send)
     }
 }
 
 /// A sender struct for sending NDI
+///
+/// All methods take `&self`, so a sender can be shared between threads, for instance in an [`Arc`].
+/// Video and audio can be sent from separate threads at the same time,
+/// frames of the same type are submitted to the SDK one at a time.
+/// A frame sent with [`Send::send_video_async()`] is owned by the sender until the SDK is done with it,
+/// so it stays valid even when a call on another thread is the synchronizing event.
+/// Use [`Send::handle()`] to poll metadata and tally from other threads while frames are being sent.
 pub struct Send {
-    p_instance: Arc<OnDrop<NDIlib_send_instance_t>>,
+    handle: SendHandle,
+    failover: Mutex<Option<Source>>,
+    /// The frame last sent with [`Send::send_video_async()`], until the SDK is done reading from it
+    video: Mutex<Option<OwnedVideoFrame>>,
+    audio: Mutex<()>,
 }
 
 impl Send {
     /// Create a new instance with default parameters
     ///
     /// It is recommended to use [`SendBuilder`] instead
-    pub fn new() -> Result<Self, SendCreateError> {
+    pub fn new() -> Result<Self, Error> {
+        let runtime = NdiRuntime::initialize()?;
         let p_instance = unsafe { NDIlib_send_create(null()) };
 
         if p_instance.is_null() {
-            return Err(SendCreateError);
+            return Err(Error::CreateFailed("Send"));
         }
 
-        Ok(Self {
-            p_instance: Arc::new(OnDrop::new(p_instance, |s| unsafe {
-                NDIlib_send_destroy(s)
-            })),
-        })
+        Ok(Self::from_instance(p_instance, runtime))
     }
 
-    fn with_settings(settings: NDIlib_send_create_t) -> Result<Self, SendCreateError> {
+    fn with_settings(settings: NDIlib_send_create_t) -> Result<Self, Error> {
+        let runtime = NdiRuntime::initialize()?;
         let p_instance = unsafe { NDIlib_send_create(&settings) };
 
         if p_instance.is_null() {
-            return Err(SendCreateError);
+            return Err(Error::CreateFailed("Send"));
         }
 
-        Ok(Self {
-            p_instance: Arc::new(OnDrop::new(p_instance, |s| unsafe {
-                NDIlib_send_destroy(s)
-            })),
-        })
+        Ok(Self::from_instance(p_instance, runtime))
     }
 
-    /// Get the current tally
-    ///
-    /// the return value is whether Tally was actually updated or not
-    pub fn get_tally(&self, tally: &mut Tally, timeout_ms: u32) -> bool {
-        unsafe {
-            let p_tally = *tally;
-            let is_updated =
-                NDIlib_send_get_tally(**self.p_instance, &mut p_tally.into(), timeout_ms);
-            is_updated
+    fn from_instance(p_instance: NDIlib_send_instance_t, runtime: NdiRuntime) -> Self {
+        Self {
+            handle: SendHandle {
+                p_instance: Arc::new(
+                    OnDrop::new(p_instance, |s| unsafe { NDIlib_send_destroy(s) })
+                        .with_runtime(runtime),
+                ),
+                guard: Arc::new(Mutex::new(())),
+                tally: Arc::new(TallyState::new()),
+                seen: AtomicU64::new(0),
+            },
+            failover: Mutex::new(None),
+            video: Mutex::new(None),
+            audio: Mutex::new(()),
         }
     }
 
-    /// This allows you to receive metadata from the other end of the connection
-    pub fn capture(&self, meta_data: &mut Option<MetaData>, timeout_ms: u32) -> FrameType {
-        unsafe {
-            let mut p_meta = if let Some(metadata) = meta_data {
-                MaybeUninit::new(metadata.p_instance)
-            } else {
-                MaybeUninit::uninit()
-            };
-            let frametype = NDIlib_send_capture(**self.p_instance, p_meta.as_mut_ptr(), timeout_ms);
+    /// Get a [`SendHandle`] for polling metadata and tally from another thread
+    pub fn handle(&self) -> SendHandle {
+        self.handle.clone()
+    }
 
-            *meta_data = Some(MetaData::from_binding_send(
-                Arc::clone(&self.p_instance),
-                p_meta.assume_init(),
-            ));
+    /// Wait for the tally to change
+    ///
+    /// See [`SendHandle::get_tally()`]
+    pub fn get_tally(&self, timeout: Duration) -> Option<Tally> {
+        self.handle.get_tally(timeout)
+    }
 
-            let res: FrameType = FrameType::try_from(frametype).unwrap();
+    /// Watch the tally on a background thread and call `callback` whenever it changes
+    ///
+    /// See [`TallyWatcher`]
+    pub fn watch_tally<F>(&self, callback: F) -> TallyWatcher
+    where
+        F: FnMut(TallyEvent) + core::marker::Send + 'static,
+    {
+        TallyWatcher::new(self.handle(), callback)
+    }
 
-            res
-        }
+    /// Receive metadata from the other end of the connection
+    ///
+    /// See [`SendHandle::capture_metadata()`]
+    pub fn capture_metadata(&self, timeout: Duration) -> Result<Option<MetaData>, Error> {
+        self.handle.capture_metadata(timeout)
+    }
+
+    /// Iterate over the metadata received from the other end of the connection
+    ///
+    /// See [`IncomingMetadata`]
+    pub fn incoming_metadata(&self) -> IncomingMetadata {
+        self.handle.incoming_metadata()
     }
 
     /// Retrieve the source information for the given sender instance.
-    pub fn get_source(&self) -> Source {
-        let instance = unsafe { *NDIlib_send_get_source_name(**self.p_instance) };
-        let parent = SourceParent::Send(Arc::clone(&self.p_instance));
-        Source::from_binding(parent, instance)
+    ///
+    /// See [`SendHandle::get_source()`]
+    pub fn get_source(&self) -> Option<Source> {
+        self.handle.get_source()
     }
 
     /// This will add a metadata frame
     pub fn send_metadata(&self, metadata: &MetaData) {
-        unsafe {
-            NDIlib_send_send_metadata(**self.p_instance, &metadata.p_instance);
-        }
+        self.handle.send_metadata(metadata)
     }
 
     /// This will add an audio frame
-    pub fn send_audio(&self, audio_data: &AudioData) {
+    ///
+    /// Accepts an [`AudioFrameRef`] or a reference to an [`AudioData`]
+    pub fn send_audio<'a>(&self, audio_data: impl Into<AudioFrameRef<'a>>) {
+        let audio_data = audio_data.into();
+        let _lock = self.audio.lock().unwrap_or_else(PoisonError::into_inner);
         unsafe {
-            NDIlib_send_send_audio_v3(**self.p_instance, &audio_data.p_instance);
+            NDIlib_send_send_audio_v3(**self.handle.p_instance, &audio_data.p_instance);
         }
     }
 
+    /// This will add an audio frame with interleaved samples
+    pub fn send_audio_interleaved<T: InterleavedSample>(&self, audio_data: &InterleavedAudio<T>) {
+        let _lock = self.audio.lock().unwrap_or_else(PoisonError::into_inner);
+        unsafe { T::send(**self.handle.p_instance, audio_data) };
+    }
+
     /// This will add a video frame
-    pub fn send_video(&self, video_data: &VideoData) {
+    ///
+    /// Accepts a [`VideoFrameRef`] or a reference to a [`VideoData`]
+    pub fn send_video<'a>(&self, video_data: impl Into<VideoFrameRef<'a>>) {
+        let video_data = video_data.into();
+        let mut pending = self.video.lock().unwrap_or_else(PoisonError::into_inner);
         unsafe {
-            NDIlib_send_send_video_v2(**self.p_instance, &video_data.p_instance);
+            NDIlib_send_send_video_v2(**self.handle.p_instance, &video_data.p_instance);
         }
+        // sending synchronously releases the previous asynchronous frame
+        *pending = None;
     }
 
     /// This will add a video frame and will return immediately, having scheduled the frame to be displayed.
@@ -214,11 +278,287 @@ impl Send {
     /// Synchronizing events are :
     /// - a call to `send_video`
     /// - a call to `send_video_async` with another frame to be sent
-    /// - a call to `send_video` with p_video_data=NULL
-    /// - Dropping a [`Send`] instance
-    pub fn send_video_async(&self, video_data: &VideoData) {
+    /// - a call to `flush_video_async`
+    /// - Dropping the sender
+    ///
+    /// The sender takes ownership of the frame and keeps it until the next synchronizing event,
+    /// so the SDK never reads from freed memory. The previous frame is released by this call
+    /// and returned, so its buffer can be reused to render the next frame.
+    pub fn send_video_async(&self, video_data: OwnedVideoFrame) -> Option<OwnedVideoFrame> {
+        let mut pending = self.video.lock().unwrap_or_else(PoisonError::into_inner);
+        let frame = VideoFrameRef::from(&video_data);
         unsafe {
-            NDIlib_send_send_video_async_v2(**self.p_instance, &video_data.p_instance);
+            NDIlib_send_send_video_async_v2(**self.handle.p_instance, &frame.p_instance);
+        }
+        pending.replace(video_data)
+    }
+
+    /// Wait until the SDK is done with the frame sent by [`Send::send_video_async()`] and return it
+    pub fn flush_video_async(&self) -> Option<OwnedVideoFrame> {
+        let mut pending = self.video.lock().unwrap_or_else(PoisonError::into_inner);
+        // sending a null frame synchronizes with any frame that is still being sent
+        unsafe {
+            NDIlib_send_send_video_async_v2(**self.handle.p_instance, null());
+        }
+        pending.take()
+    }
+
+    /// Assign a fail-over source for this sender.
+    ///
+    /// If this sender was to fail, any receivers would automatically switch over to the fail-over source,
+    /// unless this sender then came back online. Specify `None` to clear the fail-over source.
+    /// The [`Source`] is kept alive for as long as it is referenced by this sender.
+    pub fn set_failover(&self, failover: Option<&Source>) {
+        let mut current = self.failover.lock().unwrap_or_else(PoisonError::into_inner);
+        let failover = failover.cloned();
+        let p_failover = failover
+            .as_ref()
+            .map_or(null(), |source| &source.p_instance as *const _);
+        unsafe {
+            NDIlib_send_set_failover(**self.handle.p_instance, p_failover);
+        }
+        *current = failover;
+    }
+
+    /// Add a connection metadata string to the list of what is sent on each new connection.
+    ///
+    /// See [`SendHandle::add_connection_metadata()`]
+    pub fn add_connection_metadata(&self, metadata: &MetaData) {
+        self.handle.add_connection_metadata(metadata)
+    }
+
+    /// Clear all connection metadata
+    pub fn clear_connection_metadata(&self) {
+        self.handle.clear_connection_metadata()
+    }
+
+    /// Get the current number of receivers connected to this source.
+    ///
+    /// See [`SendHandle::get_no_connections()`]
+    pub fn get_no_connections(&self, timeout_ms: u32) -> u32 {
+        self.handle.get_no_connections(timeout_ms)
+    }
+}
+
+/// The tally of a sender, shared by all of its handles
+///
+/// The SDK only reports whether the tally changed since it was last retrieved from the instance,
+/// so a single handle polls the SDK at a time and every change is counted here,
+/// which lets each handle see every change no matter which handle polled it.
+struct TallyState {
+    /// The number of changes so far and the last known tally
+    current: Mutex<(u64, Tally)>,
+    changed: Condvar,
+    /// Held by the handle which is polling the SDK
+    poll: Mutex<()>,
+}
+
+impl TallyState {
+    fn new() -> Self {
+        Self {
+            current: Mutex::new((0, Tally::new())),
+            changed: Condvar::new(),
+            poll: Mutex::new(()),
+        }
+    }
+
+    fn lock(&self) -> std::sync::MutexGuard<'_, (u64, Tally)> {
+        self.current.lock().unwrap_or_else(PoisonError::into_inner)
+    }
+
+    /// The number of changes so far
+    fn changes(&self) -> u64 {
+        self.lock().0
+    }
+
+    /// The last known tally
+    fn last(&self) -> Tally {
+        self.lock().1
+    }
+
+    /// Wait up to `timeout` for a change which `seen` hasn't seen yet
+    ///
+    /// `poll` waits up to the given time for the SDK to report a change,
+    /// it is only called by one handle at a time while the others wait for its result.
+    fn wait(
+        &self,
+        seen: &AtomicU64,
+        timeout: Duration,
+        mut poll: impl FnMut(Duration) -> Option<Tally>,
+    ) -> Option<Tally> {
+        let deadline = Instant::now() + timeout;
+        let mut current = self.lock();
+        loop {
+            // another handle may already have polled the change
+            if current.0 != seen.load(Ordering::Relaxed) {
+                seen.store(current.0, Ordering::Relaxed);
+                return Some(current.1);
+            }
+
+            let remaining = deadline.saturating_duration_since(Instant::now());
+            if let Ok(_poll) = self.poll.try_lock() {
+                drop(current);
+                let tally = poll(remaining);
+
+                current = self.lock();
+                if let Some(tally) = tally {
+                    current.0 += 1;
+                    current.1 = tally;
+                }
+                // wake up the waiting handles, so one of them takes over polling
+                self.changed.notify_all();
+                if tally.is_none() && deadline <= Instant::now() {
+                    return None;
+                }
+            } else if remaining.is_zero() {
+                return None;
+            } else {
+                current = self
+                    .changed
+                    .wait_timeout(current, remaining)
+                    .unwrap_or_else(PoisonError::into_inner)
+                    .0;
+            }
+        }
+    }
+}
+
+/// A cheap, cloneable handle to a [`Send`] for metadata and tally
+///
+/// This allows polling metadata and tally on other threads while a render thread pushes frames.
+/// The handle keeps the sender alive, but can't send video or audio itself.
+pub struct SendHandle {
+    pub(crate) p_instance: Arc<OnDrop<NDIlib_send_instance_t>>,
+    guard: Arc<Mutex<()>>,
+    tally: Arc<TallyState>,
+    /// The number of tally changes this handle has seen
+    seen: AtomicU64,
+}
+
+impl Clone for SendHandle {
+    /// The new handle sees tally changes which happen after it was created
+    fn clone(&self) -> Self {
+        Self {
+            p_instance: Arc::clone(&self.p_instance),
+            guard: Arc::clone(&self.guard),
+            tally: Arc::clone(&self.tally),
+            seen: AtomicU64::new(self.tally.changes()),
+        }
+    }
+}
+
+unsafe impl core::marker::Send for SendHandle {}
+unsafe impl core::marker::Sync for SendHandle {}
+
+impl SendHandle {
+    /// Wait for the tally to change
+    ///
+    /// Returns the new tally if it changed within the timeout, and `None` otherwise.
+    /// A timeout of 0 polls whether the tally changed since it was last retrieved by this handle.
+    /// Each handle, including every clone and [`TallyWatcher`], sees every change once,
+    /// so they can be used side by side.
+    pub fn get_tally(&self, timeout: Duration) -> Option<Tally> {
+        self.tally.wait(&self.seen, timeout, |timeout| {
+            let mut tally: NDIlib_tally_t = Tally::new().into();
+            let changed = unsafe {
+                NDIlib_send_get_tally(**self.p_instance, &mut tally, timeout_ms(timeout))
+            };
+            if changed {
+                Some(Tally::from(tally))
+            } else {
+                None
+            }
+        })
+    }
+
+    /// The tally as of the last change retrieved by any handle
+    pub fn last_tally(&self) -> Tally {
+        self.tally.last()
+    }
+
+    /// Watch the tally on a background thread and call `callback` whenever it changes
+    ///
+    /// See [`TallyWatcher`]
+    pub fn watch_tally<F>(&self, callback: F) -> TallyWatcher
+    where
+        F: FnMut(TallyEvent) + core::marker::Send + 'static,
+    {
+        TallyWatcher::new(self.clone(), callback)
+    }
+
+    /// Receive metadata from the other end of the connection
+    ///
+    /// Receivers send metadata for instance for KVM, PTZ or custom commands.
+    /// Returns `None` if no metadata was received within the timeout.
+    /// The returned [`MetaData`] is freed by the SDK once it is dropped.
+    pub fn capture_metadata(&self, timeout: Duration) -> Result<Option<MetaData>, Error> {
+        let mut p_metadata = MaybeUninit::<NDIlib_metadata_frame_t>::zeroed();
+        let frame_type = unsafe {
+            NDIlib_send_capture(
+                **self.p_instance,
+                p_metadata.as_mut_ptr(),
+                timeout_ms(timeout),
+            )
+        };
+
+        // the SDK only fills in the frame if metadata was received
+        match FrameType::try_from(frame_type)? {
+            FrameType::Metadata => Ok(Some(MetaData::from_binding_send(
+                Arc::clone(&self.p_instance),
+                unsafe { p_metadata.assume_init() },
+            ))),
+            FrameType::ErrorFrame => Err(Error::NotConnected),
+            _ => Ok(None),
+        }
+    }
+
+    /// Iterate over the metadata received from the other end of the connection
+    ///
+    /// See [`IncomingMetadata`]
+    pub fn incoming_metadata(&self) -> IncomingMetadata {
+        IncomingMetadata {
+            handle: self.clone(),
+        }
+    }
+
+    /// Retrieve the source information for the given sender instance.
+    ///
+    /// Returns `None` if the SDK doesn't provide the source information.
+    pub fn get_source(&self) -> Option<Source> {
+        let instance = unsafe { NDIlib_send_get_source_name(**self.p_instance) };
+        if instance.is_null() {
+            return None;
+        }
+
+        let parent = SourceParent::Send(Arc::clone(&self.p_instance));
+        Some(Source::from_binding(parent, unsafe { *instance }))
+    }
+
+    /// This will add a metadata frame
+    pub fn send_metadata(&self, metadata: &MetaData) {
+        unsafe {
+            NDIlib_send_send_metadata(**self.p_instance, &metadata.p_instance);
+        }
+    }
+
+    /// Add a connection metadata string to the list of what is sent on each new connection.
+    ///
+    /// If someone is already connected then this string will be sent to them immediately.
+    /// Connection based metadata is data that is sent automatically each time a new connection is received,
+    /// which is commonly used to advertise capabilities like a web control URL or product information.
+    /// To reset them you need to clear them all and set them up again using [`SendHandle::clear_connection_metadata()`]
+    pub fn add_connection_metadata(&self, metadata: &MetaData) {
+        let _lock = self.guard.lock().unwrap_or_else(PoisonError::into_inner);
+        unsafe {
+            NDIlib_send_add_connection_metadata(**self.p_instance, &metadata.p_instance);
+        }
+    }
+
+    /// Clear all connection metadata
+    pub fn clear_connection_metadata(&self) {
+        let _lock = self.guard.lock().unwrap_or_else(PoisonError::into_inner);
+        unsafe {
+            NDIlib_send_clear_connection_metadata(**self.p_instance);
         }
     }
 
@@ -230,11 +570,188 @@ impl Send {
     pub fn get_no_connections(&self, timeout_ms: u32) -> u32 {
         unsafe { NDIlib_send_get_no_connections(**self.p_instance, timeout_ms) as _ }
     }
+}
+
+impl Drop for Send {
+    fn drop(&mut self) {
+        // a handle can keep the instance alive, so the SDK has to be done with the frame before it is freed
+        let pending = self.video.get_mut().unwrap_or_else(PoisonError::into_inner);
+        if pending.is_some() {
+            self.flush_video_async();
+        }
+    }
+}
+
+/// A blocking iterator over the metadata a [`Send`] receives from the other end of the connection
+///
+/// Iterating blocks until the next metadata frame arrives and ends if capturing fails.
+/// The iterator holds a [`SendHandle`], so it can be moved to another thread.
+pub struct IncomingMetadata {
+    handle: SendHandle,
+}
+
+impl Iterator for IncomingMetadata {
+    type Item = MetaData;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        loop {
+            match self.handle.capture_metadata(Duration::from_secs(1)) {
+                Ok(Some(metadata)) => return Some(metadata),
+                Ok(None) => {}
+                Err(_) => return None,
+            }
+        }
+    }
+}
+
+/// A change of the tally of a [`Send`], reported by a [`TallyWatcher`]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TallyEvent {
+    /// The sender went on or off program output
+    Program(bool),
+    /// The sender went on or off preview output
+    Preview(bool),
+}
+
+impl TallyEvent {
+    /// The events describing the change from `old` to `new`
+    fn changes(old: Tally, new: Tally) -> Vec<TallyEvent> {
+        let mut events = vec![];
+        if old.on_program != new.on_program {
+            events.push(TallyEvent::Program(new.on_program));
+        }
+        if old.on_preview != new.on_preview {
+            events.push(TallyEvent::Preview(new.on_preview));
+        }
+        events
+    }
+}
+
+/// Watches the tally of a [`Send`] on a background thread and reports each change as a [`TallyEvent`]
+///
+/// The first events report the last known tally, if the sender is already on program or preview.
+/// Watchers don't take changes away from other handles, see [`SendHandle::get_tally()`].
+/// Dropping the watcher stops the thread.
+pub struct TallyWatcher {
+    stop: Arc<AtomicBool>,
+    thread: Option<JoinHandle<()>>,
+}
+
+impl TallyWatcher {
+    /// How long the thread waits for a change before checking whether the watcher was dropped
+    const POLL_INTERVAL: Duration = Duration::from_millis(100);
+
+    /// Start watching the tally, calling `callback` on the background thread for every change
+    pub fn new<F>(handle: SendHandle, mut callback: F) -> Self
+    where
+        F: FnMut(TallyEvent) + core::marker::Send + 'static,
+    {
+        let stop = Arc::new(AtomicBool::new(false));
+        let thread = {
+            let stop = Arc::clone(&stop);
+            thread::spawn(move || {
+                let mut current = Tally::new();
+                let last = handle.last_tally();
+                TallyEvent::changes(current, last)
+                    .into_iter()
+                    .for_each(&mut callback);
+                current = last;
+                while !stop.load(Ordering::Relaxed) {
+                    if let Some(tally) = handle.get_tally(Self::POLL_INTERVAL) {
+                        TallyEvent::changes(current, tally)
+                            .into_iter()
+                            .for_each(&mut callback);
+                        current = tally;
+                    }
+                }
+            })
+        };
+
+        Self {
+            stop,
+            thread: Some(thread),
+        }
+    }
+
+    /// Start watching the tally, sending every change to the returned channel
+    pub fn channel(handle: SendHandle) -> (Self, mpsc::Receiver<TallyEvent>) {
+        let (tx, rx) = mpsc::channel();
+        let watcher = Self::new(handle, move |event| {
+            // the receiver may have been dropped before the watcher
+            let _ = tx.send(event);
+        });
+        (watcher, rx)
+    }
+}
+
+impl Drop for TallyWatcher {
+    fn drop(&mut self) {
+        self.stop.store(true, Ordering::Relaxed);
+        if let Some(thread) = self.thread.take() {
+            let _ = thread.join();
+        }
+    }
+}
+
+#[test]
+fn tally_event_changes() {
+    let off = Tally::new();
+    let program = Tally {
+        on_program: true,
+        on_preview: false,
+    };
+    let preview = Tally {
+        on_program: false,
+        on_preview: true,
+    };
+
+    assert!(TallyEvent::changes(off, off).is_empty());
+    assert_eq!(
+        TallyEvent::changes(off, program),
+        vec![TallyEvent::Program(true)]
+    );
+    assert_eq!(
+        TallyEvent::changes(program, preview),
+        vec![TallyEvent::Program(false), TallyEvent::Preview(true)]
+    );
+}
+
+#[test]
+fn tally_changes_are_seen_by_every_handle() {
+    let program = Tally {
+        on_program: true,
+        on_preview: false,
+    };
+    let tally = Arc::new(TallyState::new());
+    // the SDK reports the change to whichever handle polls first
+    let pending = Arc::new(AtomicBool::new(true));
+    let get_tally = |seen: &AtomicU64, timeout| {
+        tally.wait(seen, timeout, |_| {
+            if pending.swap(false, Ordering::Relaxed) {
+                Some(program)
+            } else {
+                None
+            }
+        })
+    };
+
+    let first = AtomicU64::new(tally.changes());
+    let second = AtomicU64::new(tally.changes());
+    thread::scope(|scope| {
+        let waiting = scope.spawn(|| get_tally(&second, Duration::from_secs(1)));
+        assert_eq!(get_tally(&first, Duration::from_secs(1)), Some(program));
+        assert_eq!(waiting.join().unwrap(), Some(program));
+    });
+
+    assert_eq!(get_tally(&first, Duration::ZERO), None);
+    assert_eq!(get_tally(&second, Duration::ZERO), None);
+    assert_eq!(tally.last(), program);
+    assert_eq!(tally.changes(), 1);
+}
 
-    // Free the buffers returned by capture for metadata
-    // pub(crate) fn free_metadata(&self, metadata: &mut MetaData) {
-    //     unsafe {
-    //         NDIlib_send_free_metadata(*self.p_instance, &metadata.p_instance);
-    //     }
-    // }
+#[test]
+fn send_is_thread_safe() {
+    fn assert_send_sync<T: core::marker::Send + Sync>() {}
+    assert_send_sync::<Send>();
+    assert_send_sync::<SendHandle>();
 }
@@ -0,0 +1,192 @@
+use super::*;
+use futures_channel::{mpsc, oneshot};
+use futures_core::Stream;
+use std::{
+    future::Future,
+    pin::Pin,
+    task::{Context, Poll},
+    thread,
+    time::Instant,
+};
+
+/// How long a capture thread blocks in the SDK before checking whether it is still needed
+const POLL_INTERVAL: Duration = Duration::from_millis(100);
+
+/// The number of frames a [`RecvStream`] buffers before new frames are dropped
+const STREAM_CAPACITY: usize = 4;
+
+/// A [`Stream`] of frames captured from a [`Recv`] on a dedicated thread
+///
+/// If the stream isn't polled fast enough, newly captured frames are dropped once a few frames are buffered,
+/// so a slow consumer doesn't hold on to an increasing amount of SDK buffers.
+/// Errors, like the connection being lost, are yielded as items and the stream continues afterwards.
+/// The capture thread stops shortly after the stream is dropped.
+pub struct RecvStream<T> {
+    rx: mpsc::Receiver<Result<T, Error>>,
+}
+
+impl<T> RecvStream<T> {
+    fn spawn<F>(mut capture: F) -> Self
+    where
+        F: FnMut(Duration) -> Result<Option<T>, Error> + core::marker::Send + 'static,
+        T: core::marker::Send + 'static,
+    {
+        let (mut tx, rx) = mpsc::channel(STREAM_CAPACITY);
+        thread::spawn(move || {
+            while !tx.is_closed() {
+                let item = match capture(POLL_INTERVAL) {
+                    Ok(Some(frame)) => Ok(frame),
+                    Ok(None) => continue,
+                    Err(e) => {
+                        // the SDK returns errors immediately, so don't spin while disconnected
+                        thread::sleep(POLL_INTERVAL);
+                        Err(e)
+                    }
+                };
+                if let Err(e) = tx.try_send(item) {
+                    if e.is_disconnected() {
+                        break;
+                    }
+                }
+            }
+        });
+
+        Self { rx }
+    }
+}
+
+impl<T> Stream for RecvStream<T> {
+    type Item = Result<T, Error>;
+
+    fn poll_next(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        Pin::new(&mut self.rx).poll_next(cx)
+    }
+}
+
+impl Recv {
+    /// Capture video frames on a dedicated thread and yield them as a [`Stream`]
+    pub fn video_stream(&self) -> RecvStream<VideoData> {
+        let recv = self.share();
+        RecvStream::spawn(move |timeout| recv.capture_video(timeout))
+    }
+
+    /// Capture audio frames on a dedicated thread and yield them as a [`Stream`]
+    pub fn audio_stream(&self) -> RecvStream<AudioData> {
+        let recv = self.share();
+        RecvStream::spawn(move |timeout| recv.capture_audio(timeout))
+    }
+
+    /// Capture metadata frames on a dedicated thread and yield them as a [`Stream`]
+    pub fn metadata_stream(&self) -> RecvStream<MetaData> {
+        let recv = self.share();
+        RecvStream::spawn(move |timeout| recv.capture_metadata(timeout))
+    }
+}
+
+/// A [`Stream`] of [`SourceEvent`]s, watched by a dedicated thread
+///
+/// The first events report all sources that are already available as added.
+/// The thread stops shortly after the stream is dropped.
+pub struct SourceStream {
+    rx: mpsc::UnboundedReceiver<SourceEvent>,
+}
+
+impl Stream for SourceStream {
+    type Item = SourceEvent;
+
+    fn poll_next(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        Pin::new(&mut self.rx).poll_next(cx)
+    }
+}
+
+impl Find {
+    /// Watch the sources on the network on a dedicated thread and yield the changes as a [`Stream`]
+    pub fn source_stream(&self) -> SourceStream {
+        let find = self.share();
+        let (tx, rx) = mpsc::unbounded();
+        thread::spawn(move || {
+            let mut watcher = find.watch();
+            while !tx.is_closed() {
+                for event in watcher.poll(POLL_INTERVAL) {
+                    if tx.unbounded_send(event).is_err() {
+                        return;
+                    }
+                }
+            }
+        });
+
+        SourceStream { rx }
+    }
+}
+
+/// A [`Future`] which resolves once the tally of a [`Send`] changes
+///
+/// Resolves to `None` if the tally didn't change within the timeout.
+/// The polling thread stops shortly after the future is dropped.
+pub struct TallyFuture {
+    rx: oneshot::Receiver<Option<Tally>>,
+}
+
+impl Future for TallyFuture {
+    type Output = Option<Tally>;
+
+    fn poll(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        Pin::new(&mut self.rx)
+            .poll(cx)
+            .map(|tally| tally.unwrap_or(None))
+    }
+}
+
+impl Send {
+    /// Wait for the tally to change without blocking the current thread
+    ///
+    /// See [`SendHandle::get_tally_async()`]
+    pub fn get_tally_async(&self, timeout: Duration) -> TallyFuture {
+        self.handle().get_tally_async(timeout)
+    }
+}
+
+impl SendHandle {
+    /// Wait for the tally to change without blocking the current thread
+    ///
+    /// The tally is polled on a dedicated thread.
+    pub fn get_tally_async(&self, timeout: Duration) -> TallyFuture {
+        let handle = self.clone();
+        let (tx, rx) = oneshot::channel();
+        thread::spawn(move || {
+            let deadline = Instant::now() + timeout;
+            loop {
+                if tx.is_canceled() {
+                    return;
+                }
+                let remaining = deadline.saturating_duration_since(Instant::now());
+                if let Some(tally) = handle.get_tally(remaining.min(POLL_INTERVAL)) {
+                    let _ = tx.send(Some(tally));
+                    return;
+                }
+                if remaining.is_zero() {
+                    let _ = tx.send(None);
+                    return;
+                }
+            }
+        });
+
+        TallyFuture { rx }
+    }
+}
+
+#[test]
+fn recv_stream_yields_captured_frames() {
+    let mut frames = vec![Ok(Some(2)), Ok(None), Ok(Some(1))];
+    let mut stream = RecvStream::spawn(move |_| frames.pop().unwrap_or(Ok(None)));
+
+    let mut cx = Context::from_waker(std::task::Waker::noop());
+    let mut received = vec![];
+    while received.len() < 2 {
+        match Pin::new(&mut stream).poll_next(&mut cx) {
+            Poll::Ready(Some(frame)) => received.push(frame.unwrap()),
+            _ => thread::sleep(Duration::from_millis(1)),
+        }
+    }
+    assert_eq!(received, vec![1, 2]);
+}
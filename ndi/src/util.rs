@@ -0,0 +1,429 @@
+use super::*;
+
+/// The unsafe conversions behind [`InterleavedSample`], which can't be implemented outside of this crate
+pub(crate) mod sealed {
+    use super::*;
+
+    pub trait Sealed: Sized {
+        /// # Safety
+        ///
+        /// `src` has to describe a valid planar floating-point frame,
+        /// and `dst` has to hold `src.no_samples` samples for each of `src.no_channels` channels
+        unsafe fn to_interleaved(src: &NDIlib_audio_frame_v2_t, dst: &mut InterleavedAudio<Self>)
+        where
+            Self: InterleavedSample;
+        /// # Safety
+        ///
+        /// `dst` has to point to a planar buffer with room for all samples of `src`
+        unsafe fn from_interleaved(src: &InterleavedAudio<Self>, dst: &mut NDIlib_audio_frame_v2_t)
+        where
+            Self: InterleavedSample;
+        /// # Safety
+        ///
+        /// `send` has to be a valid sender instance
+        unsafe fn send(send: NDIlib_send_instance_t, audio: &InterleavedAudio<Self>)
+        where
+            Self: InterleavedSample;
+    }
+}
+
+/// A sample type which audio can be interleaved into
+///
+/// This is implemented for `i16`, `i32` and `f32`.
+pub trait InterleavedSample: Copy + Default + sealed::Sealed {}
+
+macro_rules! impl_interleaved_sample {
+    ($sample:ty, $frame:ident, $to:ident, $from:ident, $send:ident $(, $reference_level:ident)?) => {
+        impl InterleavedSample for $sample {}
+
+        impl sealed::Sealed for $sample {
+            unsafe fn to_interleaved(src: &NDIlib_audio_frame_v2_t, dst: &mut InterleavedAudio<Self>) {
+                let mut frame = $frame {
+                    sample_rate: src.sample_rate,
+                    no_channels: src.no_channels,
+                    no_samples: src.no_samples,
+                    timecode: src.timecode,
+                    $($reference_level: dst.reference_level,)?
+                    p_data: dst.data.as_mut_ptr(),
+                };
+                unsafe { $to(src, &mut frame) };
+            }
+
+            unsafe fn from_interleaved(src: &InterleavedAudio<Self>, dst: &mut NDIlib_audio_frame_v2_t) {
+                let frame = binding!(src, $frame $(, $reference_level)?);
+                unsafe { $from(&frame, dst) };
+            }
+
+            unsafe fn send(send: NDIlib_send_instance_t, audio: &InterleavedAudio<Self>) {
+                let frame = binding!(audio, $frame $(, $reference_level)?);
+                unsafe { $send(send, &frame) };
+            }
+        }
+    };
+}
+
+macro_rules! binding {
+    ($audio:expr, $frame:ident $(, $reference_level:ident)?) => {
+        $frame {
+            sample_rate: $audio.sample_rate as _,
+            no_channels: $audio.no_channels as _,
+            no_samples: $audio.no_samples() as _,
+            timecode: $audio.timecode,
+            $($reference_level: $audio.reference_level,)?
+            // the SDK only reads from the source buffer
+            p_data: $audio.data.as_ptr() as *mut _,
+        }
+    };
+}
+
+impl_interleaved_sample!(
+    i16,
+    NDIlib_audio_frame_interleaved_16s_t,
+    NDIlib_util_audio_to_interleaved_16s_v2,
+    NDIlib_util_audio_from_interleaved_16s_v2,
+    NDIlib_util_send_send_audio_interleaved_16s,
+    reference_level
+);
+impl_interleaved_sample!(
+    i32,
+    NDIlib_audio_frame_interleaved_32s_t,
+    NDIlib_util_audio_to_interleaved_32s_v2,
+    NDIlib_util_audio_from_interleaved_32s_v2,
+    NDIlib_util_send_send_audio_interleaved_32s,
+    reference_level
+);
+impl_interleaved_sample!(
+    f32,
+    NDIlib_audio_frame_interleaved_32f_t,
+    NDIlib_util_audio_to_interleaved_32f_v2,
+    NDIlib_util_audio_from_interleaved_32f_v2,
+    NDIlib_util_send_send_audio_interleaved_32f
+);
+
+/// An audio buffer with interleaved samples, as used by most sound card APIs
+///
+/// The samples are stored as `L0 R0 L1 R1 ...`, in contrast to the planar layout of [`AudioData`].
+///
+/// The SDK defines the floating-point range -1.0 to +1.0 as the professional reference level of +4 dBU.
+/// For integer samples the reference level specifies how many dB above +4 dBU the full range of the
+/// integer type is: when sending you usually want +0 dB, when receiving +20 dB gives you 20 dB of headroom
+/// before clipping. The reference level is ignored for `f32` samples.
+#[derive(Debug, Clone, PartialEq)]
+pub struct InterleavedAudio<T: InterleavedSample> {
+    sample_rate: u32,
+    no_channels: u32,
+    timecode: i64,
+    reference_level: i32,
+    data: Vec<T>,
+}
+
+impl<T: InterleavedSample> InterleavedAudio<T> {
+    /// Create a new interleaved audio buffer
+    ///
+    /// Returns [`Error::OutOfRange`] if `no_channels` is 0
+    /// and [`Error::InvalidLayout`] if the length of `data` is not a multiple of `no_channels`.
+    pub fn new(sample_rate: u32, no_channels: u32, data: Vec<T>) -> Result<Self, Error> {
+        if no_channels == 0 {
+            return Err(Error::OutOfRange("no_channels", 0.0));
+        }
+        if data.len() % no_channels as usize != 0 {
+            return Err(Error::InvalidLayout);
+        }
+
+        Ok(Self {
+            sample_rate,
+            no_channels,
+            timecode: NDIlib_send_timecode_synthesize,
+            reference_level: 0,
+            data,
+        })
+    }
+
+    /// Convert planar audio into interleaved samples
+    ///
+    /// See [`InterleavedAudio`] for the meaning of `reference_level`.
+    /// Returns [`Error::OutOfRange`] if the frame has no channels,
+    /// and an error if the frame has no samples, is not in a planar floating-point format
+    /// or the NDI library can't be initialized.
+    pub fn from_audio_data(audio: &AudioData, reference_level: i32) -> Result<Self, Error> {
+        if audio.no_channels() == 0 {
+            return Err(Error::OutOfRange("no_channels", 0.0));
+        }
+        // checks the format and layout before the SDK reads the samples
+        audio.planar_samples()?;
+
+        let src = audio.to_binding_v2();
+        let len = audio.no_samples() as usize * audio.no_channels() as usize;
+        let mut dst = Self {
+            sample_rate: audio.sample_rate(),
+            no_channels: audio.no_channels(),
+            timecode: audio.timecode(),
+            reference_level,
+            data: vec![T::default(); len],
+        };
+        // the SDK has to be loaded, which isn't guaranteed without a receiver or sender
+        let _runtime = NdiRuntime::initialize()?;
+        // the frame was validated above and `dst` holds all of its samples
+        unsafe { T::to_interleaved(&src, &mut dst) };
+        Ok(dst)
+    }
+
+    /// Convert the interleaved samples into planar audio, which can be sent with [`Send::send_audio()`]
+    ///
+    /// Returns an error if the NDI library can't be initialized.
+    pub fn to_audio_data(&self) -> Result<AudioData, Error> {
+        let _runtime = NdiRuntime::initialize()?;
+        let no_samples = self.no_samples();
+        let mut buffer = vec![0f32; self.data.len()];
+        let mut dst = NDIlib_audio_frame_v2_t {
+            sample_rate: self.sample_rate as _,
+            no_channels: self.no_channels as _,
+            no_samples: no_samples as _,
+            timecode: self.timecode,
+            p_data: buffer.as_mut_ptr(),
+            channel_stride_in_bytes: (no_samples as usize * std::mem::size_of::<f32>()) as _,
+            p_metadata: null(),
+            timestamp: NDIlib_recv_timestamp_undefined,
+        };
+        // `buffer` holds all samples of this frame
+        unsafe { T::from_interleaved(self, &mut dst) };
+
+        Ok(AudioData::from_planar_buffer(&dst, buffer))
+    }
+
+    /// Set the timecode of this frame in 100 ns intervals
+    pub fn set_timecode(&mut self, timecode: i64) {
+        self.timecode = timecode;
+    }
+
+    /// Set the reference level in dB, see [`InterleavedAudio`]
+    pub fn set_reference_level(&mut self, reference_level: i32) {
+        self.reference_level = reference_level;
+    }
+
+    /// The sample-rate of this buffer
+    pub fn sample_rate(&self) -> u32 {
+        self.sample_rate
+    }
+
+    /// The number of audio channels
+    pub fn no_channels(&self) -> u32 {
+        self.no_channels
+    }
+
+    /// The number of audio samples per channel
+    pub fn no_samples(&self) -> u32 {
+        self.data.len() as u32 / self.no_channels
+    }
+
+    /// The timecode of this frame in 100 ns intervals
+    pub fn timecode(&self) -> i64 {
+        self.timecode
+    }
+
+    /// The reference level in dB, see [`InterleavedAudio`]
+    pub fn reference_level(&self) -> i32 {
+        self.reference_level
+    }
+
+    /// The interleaved samples
+    pub fn data(&self) -> &[T] {
+        &self.data
+    }
+
+    /// The interleaved samples
+    pub fn data_mut(&mut self) -> &mut [T] {
+        &mut self.data
+    }
+
+    /// Take the interleaved samples out of this buffer
+    pub fn into_data(self) -> Vec<T> {
+        self.data
+    }
+}
+
+/// The memory layout of a V210 frame
+///
+/// V210 is a packed 10-bit 4:2:2 format, which stores 6 pixels in 4 little-endian 32-bit words.
+/// NDI doesn't define V210 as a FourCC type, so frames are converted to and from [`FourCCVideoType::P216`]
+/// to be sent or received without reducing them to 8 bits.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct V210Layout {
+    /// The width of the frame in pixels
+    pub width: u32,
+    /// The height of the frame in pixels
+    pub height: u32,
+    /// The inter-line stride of the frame in bytes
+    pub line_stride_in_bytes: u32,
+}
+
+impl V210Layout {
+    /// The layout of a frame with the usual stride, where every line is padded to a multiple of 48 pixels (128 bytes)
+    pub fn new(width: u32, height: u32) -> Self {
+        Self {
+            width,
+            height,
+            line_stride_in_bytes: width.div_ceil(48) * 128,
+        }
+    }
+
+    /// The size of the frame in bytes
+    pub fn size_in_bytes(&self) -> usize {
+        self.line_stride_in_bytes as usize * self.height as usize
+    }
+
+    /// The minimal stride needed to hold a line of `width` pixels
+    fn min_line_stride(&self) -> u32 {
+        self.width.div_ceil(6) * 16
+    }
+}
+
+/// Convert a V210 frame into a [`FourCCVideoType::P216`] frame which can be sent with [`Send::send_video()`]
+///
+/// Returns [`Error::InvalidLayout`] if the buffer is smaller than described by `layout`,
+/// and an error if the NDI library can't be initialized.
+pub fn v210_to_p216(
+    layout: V210Layout,
+    v210: &[u8],
+    frame_rate_n: i32,
+    frame_rate_d: i32,
+    frame_format: FrameFormatType,
+    timecode: i64,
+) -> Result<VideoData, Error> {
+    if layout.line_stride_in_bytes < layout.min_line_stride() || v210.len() < layout.size_in_bytes()
+    {
+        return Err(Error::InvalidLayout);
+    }
+
+    let src = NDIlib_video_frame_v2_t {
+        xres: layout.width as _,
+        yres: layout.height as _,
+        // the FourCC of the source is ignored, since NDI doesn't define V210
+        FourCC: FourCCVideoType::UYVY as _,
+        frame_rate_N: frame_rate_n,
+        frame_rate_D: frame_rate_d,
+        picture_aspect_ratio: 0f32,
+        frame_format_type: frame_format as _,
+        timecode,
+        // the SDK only reads from the source buffer
+        p_data: v210.as_ptr() as *mut _,
+        __bindgen_anon_1: NDIlib_video_frame_v2_t__bindgen_ty_1 {
+            line_stride_in_bytes: layout.line_stride_in_bytes as _,
+        },
+        p_metadata: null(),
+        timestamp: NDIlib_recv_timestamp_undefined,
+    };
+
+    // a Y plane followed by an interleaved UV plane, both with 16 bits per sample
+    let stride = layout.width as usize * 2;
+    let mut buffer = vec![0u8; stride * layout.height as usize * 2];
+    let mut dst = NDIlib_video_frame_v2_t {
+        FourCC: FourCCVideoType::P216 as _,
+        p_data: buffer.as_mut_ptr(),
+        __bindgen_anon_1: NDIlib_video_frame_v2_t__bindgen_ty_1 {
+            line_stride_in_bytes: stride as _,
+        },
+        ..src
+    };
+    let _runtime = NdiRuntime::initialize()?;
+    unsafe { NDIlib_util_V210_to_P216(&src, &mut dst) };
+
+    Ok(VideoData::from_binding_buffer(dst, buffer))
+}
+
+/// A V210 frame converted from a [`VideoData`] frame
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct V210Frame {
+    /// The memory layout of `data`
+    pub layout: V210Layout,
+    /// The packed V210 pixels
+    pub data: Vec<u8>,
+}
+
+/// Convert a [`FourCCVideoType::P216`] frame into V210
+///
+/// 10-bit sources are received as P216 when using [`RecvColorFormat::Best`].
+/// Returns [`Error::InvalidLayout`] if the frame isn't P216 or its stride is smaller than one line,
+/// [`Error::OutOfRange`] if its dimensions aren't positive, [`Error::NoData`] if it holds no data,
+/// and an error if the NDI library can't be initialized.
+pub fn p216_to_v210(video: &VideoData) -> Result<V210Frame, Error> {
+    let frame = &video.p_instance;
+    if frame.FourCC != FourCCVideoType::P216 as _ {
+        return Err(Error::InvalidLayout);
+    }
+    if frame.xres <= 0 {
+        return Err(Error::OutOfRange("width", frame.xres as _));
+    }
+    if frame.yres <= 0 {
+        return Err(Error::OutOfRange("height", frame.yres as _));
+    }
+    let stride = unsafe { frame.__bindgen_anon_1.line_stride_in_bytes };
+    if stride < 0 {
+        return Err(Error::OutOfRange("stride", stride as _));
+    }
+    let source = video.layout().ok_or(Error::InvalidLayout)?;
+    if (source.line_stride_in_bytes as u64) < source.width as u64 * 2 {
+        return Err(Error::InvalidLayout);
+    }
+    if video.p_data().is_null() {
+        return Err(Error::NoData);
+    }
+
+    let layout = V210Layout::new(video.width(), video.height());
+    let mut data = vec![0u8; layout.size_in_bytes()];
+    let mut dst = NDIlib_video_frame_v2_t {
+        p_data: data.as_mut_ptr(),
+        __bindgen_anon_1: NDIlib_video_frame_v2_t__bindgen_ty_1 {
+            line_stride_in_bytes: layout.line_stride_in_bytes as _,
+        },
+        p_metadata: null(),
+        ..video.p_instance
+    };
+    // a frame which wasn't received, like one from `v210_to_p216`, doesn't keep the SDK loaded
+    let _runtime = NdiRuntime::initialize()?;
+    unsafe { NDIlib_util_P216_to_V210(&video.p_instance, &mut dst) };
+
+    Ok(V210Frame { layout, data })
+}
+
+#[test]
+fn v210_layout_stride() {
+    assert_eq!(V210Layout::new(1920, 1080).line_stride_in_bytes, 5120);
+    assert_eq!(V210Layout::new(1280, 720).line_stride_in_bytes, 3456);
+    assert_eq!(V210Layout::new(1280, 720).size_in_bytes(), 3456 * 720);
+}
+
+#[test]
+fn interleaved_audio_no_samples() {
+    let audio = InterleavedAudio::new(48000, 2, vec![0i16; 960]).unwrap();
+    assert_eq!(audio.no_samples(), 480);
+    assert_eq!(audio.timecode(), NDIlib_send_timecode_synthesize);
+}
+
+#[test]
+fn interleaved_audio_rejects_empty_frames() {
+    assert!(InterleavedAudio::<i16>::new(48000, 0, vec![]).is_err());
+    assert!(InterleavedAudio::<f32>::from_audio_data(&AudioData::new(), 0).is_err());
+}
+
+#[test]
+fn p216_to_v210_checks_layout() {
+    let frame = |xres, yres, stride, p_data| {
+        let mut p_instance = VideoData::new().p_instance;
+        p_instance.xres = xres;
+        p_instance.yres = yres;
+        p_instance.FourCC = FourCCVideoType::P216 as _;
+        p_instance.__bindgen_anon_1.line_stride_in_bytes = stride;
+        p_instance.p_data = p_data;
+        VideoData::from_binding_buffer(p_instance, vec![])
+    };
+    let mut data = vec![0u8; 16 * 4 * 2];
+    let result = p216_to_v210(&frame(16, 4, 1, data.as_mut_ptr()));
+    assert!(matches!(result, Err(Error::InvalidLayout)));
+    let result = p216_to_v210(&frame(0, 4, 32, data.as_mut_ptr()));
+    assert!(matches!(result, Err(Error::OutOfRange("width", _))));
+    let result = p216_to_v210(&frame(16, -4, 32, data.as_mut_ptr()));
+    assert!(matches!(result, Err(Error::OutOfRange("height", _))));
+    let result = p216_to_v210(&frame(16, 4, 32, null_mut()));
+    assert!(matches!(result, Err(Error::NoData)));
+}
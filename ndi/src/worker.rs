@@ -0,0 +1,351 @@
+use super::*;
+use std::{
+    collections::VecDeque,
+    sync::{
+        atomic::{AtomicI64, Ordering},
+        Condvar, Mutex,
+    },
+    thread::{self, JoinHandle},
+};
+
+/// How long a capture thread blocks in the SDK before checking whether the worker was stopped
+const CAPTURE_TIMEOUT: Duration = Duration::from_millis(100);
+
+/// What a [`RecvWorker`] does with a captured frame when its queue is full
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DropPolicy {
+    /// Drop the oldest queued frame to make room for the new one
+    DropOldest,
+    /// Drop the newly captured frame
+    DropNewest,
+    /// Stop capturing until there is room in the queue
+    ///
+    /// Frames are then queued up and dropped by the SDK instead.
+    Block,
+}
+
+struct QueueState<T> {
+    frames: VecDeque<T>,
+    closed: bool,
+}
+
+/// A bounded queue between a capture thread and the consumers of a [`RecvWorker`]
+struct Queue<T> {
+    state: Mutex<QueueState<T>>,
+    changed: Condvar,
+    capacity: usize,
+    policy: DropPolicy,
+    dropped: AtomicI64,
+}
+
+impl<T> Queue<T> {
+    fn new(capacity: usize, policy: DropPolicy) -> Self {
+        Self {
+            state: Mutex::new(QueueState {
+                frames: VecDeque::with_capacity(capacity),
+                closed: false,
+            }),
+            changed: Condvar::new(),
+            capacity,
+            policy,
+            dropped: AtomicI64::new(0),
+        }
+    }
+
+    fn lock(&self) -> std::sync::MutexGuard<'_, QueueState<T>> {
+        self.state.lock().unwrap_or_else(PoisonError::into_inner)
+    }
+
+    fn is_closed(&self) -> bool {
+        self.lock().closed
+    }
+
+    fn close(&self) {
+        self.lock().closed = true;
+        self.changed.notify_all();
+    }
+
+    fn push(&self, frame: T) {
+        let mut state = self.lock();
+        if state.frames.len() >= self.capacity {
+            match self.policy {
+                DropPolicy::DropOldest => {
+                    state.frames.pop_front();
+                    self.dropped.fetch_add(1, Ordering::Relaxed);
+                }
+                DropPolicy::DropNewest => {
+                    self.dropped.fetch_add(1, Ordering::Relaxed);
+                    return;
+                }
+                DropPolicy::Block => {
+                    state = self
+                        .changed
+                        .wait_while(state, |state| {
+                            state.frames.len() >= self.capacity && !state.closed
+                        })
+                        .unwrap_or_else(PoisonError::into_inner);
+                    if state.closed {
+                        return;
+                    }
+                }
+            }
+        }
+
+        state.frames.push_back(frame);
+        drop(state);
+        self.changed.notify_all();
+    }
+
+    fn pop(&self, timeout: Duration) -> Option<T> {
+        let state = self.lock();
+        let (mut state, _) = self
+            .changed
+            .wait_timeout_while(state, timeout, |state| {
+                state.frames.is_empty() && !state.closed
+            })
+            .unwrap_or_else(PoisonError::into_inner);
+
+        let frame = state.frames.pop_front();
+        drop(state);
+        if frame.is_some() {
+            // wake up a capture thread blocked on a full queue
+            self.changed.notify_all();
+        }
+        frame
+    }
+
+    fn dropped(&self) -> i64 {
+        self.dropped.load(Ordering::Relaxed)
+    }
+}
+
+/// Frame counts of a [`RecvWorker`], see [`RecvWorker::get_performance()`]
+#[derive(Debug, Clone, Copy, Default)]
+pub struct RecvWorkerPerformance {
+    /// The frames received by the SDK
+    pub total: RecvPerformance,
+    /// The frames dropped by the SDK, before they were captured
+    pub dropped_by_sdk: RecvPerformance,
+    /// The frames dropped by the worker, because its queue was full
+    pub dropped_by_queue: RecvPerformance,
+}
+
+impl Display for RecvWorkerPerformance {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        writeln!(f, "Total:\n{}", self.total)?;
+        writeln!(f, "Dropped by SDK:\n{}", self.dropped_by_sdk)?;
+        write!(f, "Dropped by queue:\n{}", self.dropped_by_queue)
+    }
+}
+
+/// Builder struct for [`RecvWorker`]
+#[derive(Debug, Clone)]
+pub struct RecvWorkerBuilder {
+    capacity: Option<usize>,
+    drop_policy: Option<DropPolicy>,
+    video: Option<bool>,
+    audio: Option<bool>,
+    metadata: Option<bool>,
+}
+
+impl RecvWorkerBuilder {
+    /// Create a new instance of the builder
+    pub fn new() -> Self {
+        Self {
+            capacity: None,
+            drop_policy: None,
+            video: None,
+            audio: None,
+            metadata: None,
+        }
+    }
+
+    /// The number of frames of each type which are queued before the [`DropPolicy`] applies
+    ///
+    /// default: 4
+    pub fn capacity(mut self, capacity: usize) -> Self {
+        self.capacity = Some(capacity);
+        self
+    }
+
+    /// What to do with captured frames when a queue is full
+    ///
+    /// default: [`DropPolicy::DropOldest`]
+    pub fn drop_policy(mut self, drop_policy: DropPolicy) -> Self {
+        self.drop_policy = Some(drop_policy);
+        self
+    }
+
+    /// Whether to capture video frames
+    ///
+    /// default: `true`
+    pub fn video(mut self, video: bool) -> Self {
+        self.video = Some(video);
+        self
+    }
+
+    /// Whether to capture audio frames
+    ///
+    /// default: `true`
+    pub fn audio(mut self, audio: bool) -> Self {
+        self.audio = Some(audio);
+        self
+    }
+
+    /// Whether to capture metadata frames
+    ///
+    /// default: `true`
+    pub fn metadata(mut self, metadata: bool) -> Self {
+        self.metadata = Some(metadata);
+        self
+    }
+
+    /// Build the [`RecvWorker`] and start capturing from `recv`
+    pub fn build(self, recv: Recv) -> RecvWorker {
+        let capacity = self.capacity.unwrap_or(4).max(1);
+        let policy = self.drop_policy.unwrap_or(DropPolicy::DropOldest);
+        let mut threads = vec![];
+
+        let video = Arc::new(Queue::new(capacity, policy));
+        if self.video.unwrap_or(true) {
+            let recv = recv.share();
+            threads.push(spawn_capture(&video, move || {
+                recv.capture_video(CAPTURE_TIMEOUT)
+            }));
+        }
+
+        let audio = Arc::new(Queue::new(capacity, policy));
+        if self.audio.unwrap_or(true) {
+            let recv = recv.share();
+            threads.push(spawn_capture(&audio, move || {
+                recv.capture_audio(CAPTURE_TIMEOUT)
+            }));
+        }
+
+        let metadata = Arc::new(Queue::new(capacity, policy));
+        if self.metadata.unwrap_or(true) {
+            let recv = recv.share();
+            threads.push(spawn_capture(&metadata, move || {
+                recv.capture_metadata(CAPTURE_TIMEOUT)
+            }));
+        }
+
+        RecvWorker {
+            recv,
+            video,
+            audio,
+            metadata,
+            threads,
+        }
+    }
+}
+
+fn spawn_capture<T, F>(queue: &Arc<Queue<T>>, mut capture: F) -> JoinHandle<()>
+where
+    T: core::marker::Send + 'static,
+    F: FnMut() -> Result<Option<T>, Error> + core::marker::Send + 'static,
+{
+    let queue = Arc::clone(queue);
+    thread::spawn(move || {
+        while !queue.is_closed() {
+            match capture() {
+                Ok(Some(frame)) => queue.push(frame),
+                Ok(None) => {}
+                // the SDK returns errors immediately, so don't spin while disconnected
+                Err(_) => thread::sleep(CAPTURE_TIMEOUT),
+            }
+        }
+    })
+}
+
+/// Captures frames from a [`Recv`] on background threads and queues them
+///
+/// Each frame type is captured on its own thread and queued in a bounded queue,
+/// which is emptied with [`RecvWorker::recv_video()`] and its siblings from any thread.
+/// When a queue is full, frames are dropped according to the [`DropPolicy`].
+/// Dropping the worker stops the capture threads and drops all queued frames.
+pub struct RecvWorker {
+    recv: Recv,
+    video: Arc<Queue<VideoData>>,
+    audio: Arc<Queue<AudioData>>,
+    metadata: Arc<Queue<MetaData>>,
+    threads: Vec<JoinHandle<()>>,
+}
+
+impl RecvWorker {
+    /// Start capturing all frame types from `recv` with the default settings of [`RecvWorkerBuilder`]
+    pub fn new(recv: Recv) -> Self {
+        RecvWorkerBuilder::new().build(recv)
+    }
+
+    /// The receiver frames are captured from
+    pub fn recv(&self) -> &Recv {
+        &self.recv
+    }
+
+    /// Take the oldest queued video frame, waiting up to `timeout` for one to arrive
+    pub fn recv_video(&self, timeout: Duration) -> Option<VideoData> {
+        self.video.pop(timeout)
+    }
+
+    /// Take the oldest queued audio frame, waiting up to `timeout` for one to arrive
+    pub fn recv_audio(&self, timeout: Duration) -> Option<AudioData> {
+        self.audio.pop(timeout)
+    }
+
+    /// Take the oldest queued metadata frame, waiting up to `timeout` for one to arrive
+    pub fn recv_metadata(&self, timeout: Duration) -> Option<MetaData> {
+        self.metadata.pop(timeout)
+    }
+
+    /// Get the performance metrics of the receiver, together with the frames dropped by the queues
+    ///
+    /// This shows whether frames were lost in the SDK or because they weren't taken from the worker fast enough.
+    pub fn get_performance(&self) -> RecvWorkerPerformance {
+        let (total, dropped_by_sdk) = self.recv.get_performance();
+        RecvWorkerPerformance {
+            total,
+            dropped_by_sdk,
+            dropped_by_queue: RecvPerformance {
+                video_frames: self.video.dropped(),
+                audio_frames: self.audio.dropped(),
+                metadata_frames: self.metadata.dropped(),
+            },
+        }
+    }
+}
+
+impl Drop for RecvWorker {
+    fn drop(&mut self) {
+        self.video.close();
+        self.audio.close();
+        self.metadata.close();
+        for thread in self.threads.drain(..) {
+            let _ = thread.join();
+        }
+    }
+}
+
+#[test]
+fn queue_drop_policies() {
+    let oldest = Queue::new(2, DropPolicy::DropOldest);
+    (0..4).for_each(|i| oldest.push(i));
+    assert_eq!(oldest.pop(Duration::ZERO), Some(2));
+    assert_eq!(oldest.dropped(), 2);
+
+    let newest = Queue::new(2, DropPolicy::DropNewest);
+    (0..4).for_each(|i| newest.push(i));
+    assert_eq!(newest.pop(Duration::ZERO), Some(0));
+    assert_eq!(newest.dropped(), 2);
+
+    let block = Arc::new(Queue::new(1, DropPolicy::Block));
+    block.push(0);
+    let producer = {
+        let block = Arc::clone(&block);
+        thread::spawn(move || block.push(1))
+    };
+    assert_eq!(block.pop(Duration::from_secs(1)), Some(0));
+    assert_eq!(block.pop(Duration::from_secs(1)), Some(1));
+    producer.join().unwrap();
+    assert_eq!(block.dropped(), 0);
+}